@@ -0,0 +1,57 @@
+//! Benchmarks for the `Lexicon` filters over the full default word list.
+//!
+//! To compare serial and parallel filtering, save a baseline without the
+//! `rayon` feature and then compare against it with the feature enabled:
+//!
+//! ```text
+//! cargo bench --bench filters -- --save-baseline serial
+//! cargo bench --bench filters --features rayon -- --baseline serial
+//! ```
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+use lexi::wordlist::parse_list;
+use lexi::{Lexicon, VecLexicon, MAIN_WORDLIST_PATH, SWEARS_PATH};
+
+fn full_lexicon() -> VecLexicon {
+    parse_list(MAIN_WORDLIST_PATH, SWEARS_PATH).unwrap().into()
+}
+
+fn bench_filters(c: &mut Criterion) {
+    let lex = full_lexicon();
+    let mut group = c.benchmark_group("vec_filters");
+
+    group.bench_function("with_letter", |b| {
+        b.iter_batched(|| lex.clone(), |mut lex| lex.with_letter('e'), BatchSize::LargeInput)
+    });
+    group.bench_function("without_letter", |b| {
+        b.iter_batched(|| lex.clone(), |mut lex| lex.without_letter('e'), BatchSize::LargeInput)
+    });
+    group.bench_function("only_using_letters", |b| {
+        b.iter_batched(|| lex.clone(), |mut lex| lex.only_using_letters("doughby".chars()),
+                       BatchSize::LargeInput)
+    });
+    // The same filter as `only_using_letters`, checking each letter instead of
+    // using the precomputed masks.
+    group.bench_function("only_using_letters_scan", |b| {
+        b.iter_batched(|| lex.clone(), |mut lex| {
+            lex.retain_words(|word| word.chars().all(|l| "doughby".contains(l)))
+        }, BatchSize::LargeInput)
+    });
+    group.bench_function("with_length_in_range", |b| {
+        b.iter_batched(|| lex.clone(), |mut lex| lex.with_length_in_range(4..=7), BatchSize::LargeInput)
+    });
+    group.bench_function("spelling_bee_chain", |b| {
+        b.iter_batched(|| lex.clone(), |mut lex| {
+            lex.without_letter('s');
+            lex.with_letter('o');
+            lex.with_more_length(3);
+            lex.only_using_letters("doughby".chars());
+        }, BatchSize::LargeInput)
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_filters);
+criterion_main!(benches);
@@ -0,0 +1,89 @@
+//! Benchmarks comparing the `Lexicon` implementations on the full default
+//! word list, along with parsing the list itself. `cargo bench` runs these
+//! along with the filter benchmarks, or run them alone with:
+//!
+//! ```text
+//! cargo bench --bench lexicons
+//! ```
+//!
+//! The `parse` and `censor` groups also include cases with a large made-up
+//! swear list, to catch any work done once per swear.
+//!
+//! The lexicon benchmarks are named for the operation and then the
+//! implementation, like `contains/trie`, so a single implementation can be
+//! picked out with a filter like `cargo bench --bench lexicons -- /trie`.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+use lexi::wordlist::{parse_list, parse_strings};
+use lexi::{DawgLexicon, HashSetLexicon, Lexicon, TrieLexicon, VecLexicon, MAIN_WORDLIST_PATH, SWEARS_PATH};
+
+/// Words to look up, a mix of ones in the list and ones that aren't.
+const LOOKUPS: [&str; 8] = ["apple", "zymurgy", "blogger", "qwxz", "the", "acnes", "aardvark", "notaword"];
+
+fn full_words() -> Vec<String> {
+    parse_list(MAIN_WORDLIST_PATH, SWEARS_PATH).unwrap().default_list()
+}
+
+/// Runs the `contains`, `with_letter`, and `only_using_letters` benchmarks on
+/// one implementation, built from the given words.
+fn bench_lexicon<L: Lexicon + Clone>(c: &mut Criterion, name: &str, lex: L) {
+    c.bench_function(&format!("contains/{}", name), |b| {
+        b.iter(|| LOOKUPS.iter().filter(|word| lex.contains(black_box(word))).count())
+    });
+    c.bench_function(&format!("with_letter/{}", name), |b| {
+        b.iter_batched(|| lex.clone(), |mut lex| lex.with_letter('e'), BatchSize::LargeInput)
+    });
+    c.bench_function(&format!("only_using_letters/{}", name), |b| {
+        b.iter_batched(|| lex.clone(), |mut lex| lex.only_using_letters("doughby".chars()),
+                       BatchSize::LargeInput)
+    });
+}
+
+fn bench_lexicons(c: &mut Criterion) {
+    let words = full_words();
+    bench_lexicon(c, "vec", VecLexicon::new(words.clone()));
+    bench_lexicon(c, "hashset", HashSetLexicon::new(words.clone()));
+    bench_lexicon(c, "trie", TrieLexicon::new(words.clone()));
+    bench_lexicon(c, "dawg", DawgLexicon::new(words));
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let main = std::fs::read_to_string(MAIN_WORDLIST_PATH).unwrap();
+    let swears = std::fs::read_to_string(SWEARS_PATH).unwrap();
+    let mut group = c.benchmark_group("parse");
+
+    group.bench_function("parse_list", |b| b.iter(|| parse_list(MAIN_WORDLIST_PATH, SWEARS_PATH).unwrap()));
+    group.bench_function("parse_strings", |b| b.iter(|| parse_strings(&main, &swears).unwrap()));
+    group.bench_function("build/vec", |b| {
+        b.iter_batched(full_words, VecLexicon::new, BatchSize::LargeInput)
+    });
+    group.bench_function("build/trie", |b| {
+        b.iter_batched(full_words, TrieLexicon::new, BatchSize::LargeInput)
+    });
+
+    // Parsing against a large swear list, which is only fast if the swears
+    // are looked up in a set.
+    let many: Vec<String> = (0..50_000).map(|i| format!("word{}", i)).collect();
+    let many_swears: Vec<String> = (0..5_000).map(|i| format!("word{}", i * 10)).collect();
+    let (many, many_swears) = (many.join("\n"), many_swears.join("\n"));
+    group.bench_function("large_swear_list", |b| b.iter(|| parse_strings(&many, &many_swears).unwrap()));
+
+    group.finish();
+}
+
+fn bench_censor(c: &mut Criterion) {
+    let list = parse_list(MAIN_WORDLIST_PATH, SWEARS_PATH).unwrap();
+    let swears: Vec<String> = (0..50_000).map(|i| format!("swear{}", i)).collect();
+    let large = parse_strings("apple\n", &swears.join("\n")).unwrap();
+    let mut group = c.benchmark_group("censor");
+
+    group.bench_function("default_list", |b| b.iter(|| list.censor(black_box("what the fuck, man"))));
+    // Each call should cost the length of the text, not of the swear list.
+    group.bench_function("large_swear_list", |b| b.iter(|| large.censor(black_box("an apple, swear10!"))));
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_lexicons, bench_parse, bench_censor);
+criterion_main!(benches);
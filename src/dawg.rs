@@ -0,0 +1,354 @@
+//! Implements `Lexicon` with a directed acyclic word graph (DAWG), also known
+//! as a minimal acyclic finite state automaton. This is a trie in which
+//! identical subtrees are merged, so words share storage for common suffixes
+//! as well as common prefixes: "walking", "talking", and "balking" all share
+//! the nodes for "alking". For English word lists, this takes a small fraction
+//! of the memory of a `TrieLexicon` while keeping O(k) `contains`, where k is
+//! the length of the queried word.
+//!
+//! The graph can't easily be edited in place, since nodes are shared between
+//! words. Filtering operations therefore collect the remaining words and
+//! rebuild the graph from scratch, which is O(n) like the other lexicons but
+//! with a noticeably larger constant. For heavy filtering, convert to a
+//! `VecLexicon` first.
+//!
+//! Like `VecLexicon`, this lexicon is case-insensitive, and converts everything
+//! to lowercase internally.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeBounds;
+
+use crate::letters;
+use crate::lexicon::Lexicon;
+use crate::wordle::{self, Feedback};
+
+/// The index of the root node, which represents the empty prefix.
+const ROOT: usize = 0;
+
+/// A single state in the graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+struct DawgNode {
+    /// The letters that can follow this state and the nodes they lead to,
+    /// sorted by letter.
+    edges: Vec<(char, usize)>,
+    /// Whether the letters leading to this state spell a word.
+    is_final: bool,
+}
+
+impl DawgNode {
+    /// Returns the node reached by following the given letter, if any.
+    fn next(&self, letter: char) -> Option<usize> {
+        self.edges
+            .binary_search_by_key(&letter, |&(l, _)| l)
+            .ok()
+            .map(|i| self.edges[i].1)
+    }
+}
+
+/// A list of words stored as a minimal directed acyclic word graph.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DawgLexicon {
+    /// The states of the graph, with the root at index `ROOT`.
+    nodes: Vec<DawgNode>,
+    /// The number of words in the graph.
+    len: usize,
+}
+
+impl Default for DawgLexicon {
+    fn default() -> Self {
+        DawgLexicon{nodes: vec![DawgNode::default()], len: 0}
+    }
+}
+
+impl DawgLexicon {
+    /// Creates a new lexicon with the given words, in lowercase. The words
+    /// don't need to be sorted.
+    pub fn new(words: Vec<String>) -> DawgLexicon {
+        let mut words: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+        words.sort();
+        words.dedup();
+        DawgLexicon::from_sorted(&words)
+    }
+
+    /// Builds the graph for the given words, which must be sorted and unique,
+    /// following the incremental algorithm of Daciuk et al. (2000). Each word
+    /// is added as a fresh chain of nodes after its longest common prefix
+    /// with the previous word. Once a word is done, the part of the previous
+    /// word's chain that no later word can extend is merged with any
+    /// identical nodes that are already in the graph.
+    fn from_sorted(words: &[String]) -> DawgLexicon {
+        let mut builder = DawgBuilder{
+            nodes: vec![DawgNode::default()],
+            unchecked: vec![],
+            register: HashMap::new(),
+        };
+        let mut previous: &str = "";
+        for word in words {
+            let common = word.chars().zip(previous.chars()).take_while(|(a, b)| a == b).count();
+            builder.minimize(common);
+
+            let mut node = builder.unchecked.last().map_or(ROOT, |&(_, _, child)| child);
+            for letter in word.chars().skip(common) {
+                let child = builder.nodes.len();
+                builder.nodes.push(DawgNode::default());
+                builder.nodes[node].edges.push((letter, child));
+                builder.unchecked.push((node, letter, child));
+                node = child;
+            }
+            builder.nodes[node].is_final = true;
+            previous = word;
+        }
+        builder.minimize(0);
+
+        DawgLexicon{nodes: builder.compact(), len: words.len()}
+    }
+
+    /// Returns all of the words in the lexicon, in alphabetical order.
+    fn words(&self) -> Vec<String> {
+        let mut words = Vec::with_capacity(self.len);
+        self.collect_words(ROOT, &mut String::new(), &mut words);
+        words
+    }
+
+    /// Pushes every word reachable from the given node onto `words`, in
+    /// alphabetical order. `prefix` must hold the letters leading to the node.
+    fn collect_words(&self, node: usize, prefix: &mut String, words: &mut Vec<String>) {
+        if self.nodes[node].is_final {
+            words.push(prefix.clone());
+        }
+        for &(letter, child) in &self.nodes[node].edges {
+            prefix.push(letter);
+            self.collect_words(child, prefix, words);
+            prefix.pop();
+        }
+    }
+
+    /// Keeps only the words for which `keep` returns `true`, by rebuilding
+    /// the graph from the words that remain.
+    fn retain<F: Fn(&str) -> bool>(&mut self, keep: F) {
+        let words: Vec<String> = self.words().into_iter().filter(|word| keep(word)).collect();
+        *self = DawgLexicon::from_sorted(&words);
+    }
+
+    /// Returns the number of nodes in the graph, as a measure of its size.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+/// The state of a graph under construction.
+struct DawgBuilder {
+    /// Every node created so far, including those that have been replaced by
+    /// an identical node and are no longer reachable.
+    nodes: Vec<DawgNode>,
+    /// The chain of edges for the last word added, as (parent, letter, child),
+    /// whose children haven't yet been checked against the register.
+    unchecked: Vec<(usize, char, usize)>,
+    /// The nodes that have been checked, keyed by their contents.
+    register: HashMap<DawgNode, usize>,
+}
+
+impl DawgBuilder {
+    /// Checks the unchecked nodes deeper than `depth` against the register,
+    /// deepest first, merging each with an identical registered node if
+    /// there is one and registering it otherwise.
+    fn minimize(&mut self, depth: usize) {
+        while self.unchecked.len() > depth {
+            let (parent, letter, child) = self.unchecked.pop().unwrap();
+            match self.register.get(&self.nodes[child]) {
+                Some(&existing) => {
+                    // The edge to the child is always the parent's last, since
+                    // words are added in sorted order.
+                    let last = self.nodes[parent].edges.last_mut().unwrap();
+                    debug_assert_eq!(last.0, letter);
+                    last.1 = existing;
+                }
+                None => {
+                    self.register.insert(self.nodes[child].clone(), child);
+                }
+            }
+        }
+    }
+
+    /// Returns the nodes reachable from the root, renumbered so that they
+    /// appear in the order they're first reached by a depth-first search.
+    fn compact(self) -> Vec<DawgNode> {
+        let mut new_index = HashMap::new();
+        let mut order = vec![];
+        let mut stack = vec![ROOT];
+        while let Some(node) = stack.pop() {
+            if new_index.contains_key(&node) {
+                continue;
+            }
+            new_index.insert(node, order.len());
+            order.push(node);
+            for &(_, child) in self.nodes[node].edges.iter().rev() {
+                stack.push(child);
+            }
+        }
+
+        order
+            .into_iter()
+            .map(|old| {
+                let node = &self.nodes[old];
+                DawgNode{
+                    edges: node.edges.iter().map(|&(l, child)| (l, new_index[&child])).collect(),
+                    is_final: node.is_final,
+                }
+            })
+            .collect()
+    }
+}
+
+impl From<Vec<String>> for DawgLexicon {
+    fn from(words: Vec<String>) -> Self {
+        DawgLexicon::new(words)
+    }
+}
+
+impl IntoIterator for DawgLexicon {
+    type Item = String;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    /// Iterates over the words in alphabetical order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.words().into_iter()
+    }
+}
+
+impl Lexicon for DawgLexicon {
+    /// Returns `true` if the graph contains the given word and `false`
+    /// otherwise. The query is lowercased first, like the stored words.
+    fn contains(&self, word: &str) -> bool {
+        let mut node = ROOT;
+        for letter in word.to_lowercase().chars() {
+            node = match self.nodes[node].next(letter) {
+                Some(next) => next,
+                None => return false,
+            };
+        }
+        self.nodes[node].is_final
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn with_letter(&mut self, letter: char) {
+        let letter = letters::to_lowercase(letter);
+        self.retain(|word| word.contains(letter));
+    }
+
+    fn without_letter(&mut self, letter: char) {
+        let letter = letters::to_lowercase(letter);
+        self.retain(|word| !word.contains(letter));
+    }
+
+    fn only_using_letters<T: IntoIterator<Item = char>>(&mut self, letters: T) {
+        let string: String = letters.into_iter().map(letters::to_lowercase).collect();
+        self.retain(|word| word.chars().all(|l| string.contains(l)));
+    }
+
+    fn anagrams_of(&mut self, letters: &str) {
+        let available = letters::letter_counts(&letters.to_lowercase());
+        self.retain(|word| letters::fits_within(word, &available));
+    }
+
+    fn with_pattern(&mut self, pattern: &str) {
+        let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+        self.retain(|word| letters::matches_pattern(word, &pattern, '.'));
+    }
+
+    fn with_letter_at(&mut self, index: usize, letter: char) {
+        let letter = letters::to_lowercase(letter);
+        self.retain(|word| word.chars().nth(index) == Some(letter));
+    }
+
+    fn apply_wordle_guess(&mut self, guess: &str, feedback: &[Feedback]) {
+        let guess = guess.to_lowercase();
+        self.retain(|word| wordle::is_consistent(word, &guess, feedback));
+    }
+
+    fn starts_with(&mut self, prefix: &str) {
+        let prefix = prefix.to_lowercase();
+        self.retain(|word| word.starts_with(&prefix));
+    }
+
+    fn ends_with(&mut self, suffix: &str) {
+        let suffix = suffix.to_lowercase();
+        self.retain(|word| word.ends_with(&suffix));
+    }
+
+    fn containing(&mut self, substring: &str) {
+        let substring = substring.to_lowercase();
+        self.retain(|word| word.contains(&substring));
+    }
+
+    fn exclude_words<'a, I: IntoIterator<Item = &'a str>>(&mut self, words: I) {
+        let excluded: HashSet<String> = words.into_iter().map(str::to_lowercase).collect();
+        self.retain(|word| !excluded.contains(word));
+    }
+
+    fn retain_words<F: Fn(&str) -> bool>(&mut self, predicate: F) {
+        self.retain(predicate);
+    }
+
+    fn with_exact_length(&mut self, length: usize) {
+        self.retain(|word| word.chars().count() == length);
+    }
+
+    fn with_more_length(&mut self, length: usize) {
+        self.retain(|word| word.chars().count() > length);
+    }
+
+    fn with_less_length(&mut self, length: usize) {
+        self.retain(|word| word.chars().count() < length);
+    }
+
+    fn with_length_in_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.retain(|word| range.contains(&word.chars().count()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lexicon(words: &[&str]) -> DawgLexicon {
+        DawgLexicon::new(words.iter().map(|&w| String::from(w)).collect())
+    }
+
+    #[test]
+    fn test_contains() {
+        let lex = lexicon(&["walking", "Talking", "balking", "walk", "talk"]);
+        assert_eq!(lex.len(), 5);
+        assert!(lex.contains("walking"));
+        assert!(lex.contains("TALKING"));
+        assert!(lex.contains("talk"));
+        assert!(!lex.contains("balk"));
+        assert!(!lex.contains("walkin"));
+        assert!(!lex.contains(""));
+        assert_eq!(lex.into_iter().collect::<Vec<String>>(),
+                   vec!["balking", "talk", "talking", "walk", "walking"]);
+    }
+
+    #[test]
+    fn test_suffixes_are_shared() {
+        let lex = lexicon(&["walking", "talking", "balking"]);
+        // The root, then a single node after the first letter, then one for
+        // each letter of "alking".
+        assert_eq!(lex.node_count(), 1 + 1 + 6);
+    }
+
+    #[test]
+    fn test_filters_rebuild() {
+        let mut lex = lexicon(&["walking", "talking", "balking", "walk", "talk", "cat"]);
+        lex.starts_with("ta");
+        assert_eq!(lex, lexicon(&["talking", "talk"]));
+        lex.with_more_length(4);
+        assert_eq!(lex, lexicon(&["talking"]));
+        lex.without_letter('k');
+        assert_eq!(lex, DawgLexicon::default());
+        assert!(lex.is_empty());
+    }
+}
@@ -0,0 +1,49 @@
+//! The error type for reading word lists, so that callers can tell a list
+//! that couldn't be read apart from one that was read but isn't valid.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// The result of reading or parsing a word list.
+pub type Result<T> = std::result::Result<T, LexiError>;
+
+/// An error from reading or parsing a word list.
+#[derive(Debug)]
+pub enum LexiError {
+    /// The list couldn't be read at all, such as because the file doesn't
+    /// exist.
+    Io(io::Error),
+    /// A line of the list was read but isn't valid, such as because it isn't
+    /// UTF-8 or has a frequency that isn't a number.
+    Parse {
+        /// The line the problem is on, counting from 1.
+        line: usize,
+        /// What's wrong with the line.
+        message: String,
+    },
+}
+
+impl fmt::Display for LexiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexiError::Io(error) => write!(f, "{}", error),
+            LexiError::Parse{line, message} => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl Error for LexiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LexiError::Io(error) => Some(error),
+            LexiError::Parse{..} => None,
+        }
+    }
+}
+
+impl From<io::Error> for LexiError {
+    fn from(error: io::Error) -> Self {
+        LexiError::Io(error)
+    }
+}
@@ -0,0 +1,202 @@
+//! Implements `Lexicon` using a `HashSet` to store strings. This provides O(1)
+//! performance for `contains`, which makes it the best choice when lookups are
+//! the only hot operation, as in a spellchecker. The filtering operations are
+//! O(n) in the size of the lexicon, like `VecLexicon`'s.
+//!
+//! This lexicon is case-insensitive, and converts everything to lowercase internally.
+
+use std::collections::HashSet;
+use std::ops::RangeBounds;
+
+use crate::letters;
+use crate::lexicon::Lexicon;
+use crate::veclexicon::VecLexicon;
+use crate::wordle::{self, Feedback};
+
+/// An unordered set of words.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HashSetLexicon {
+    /// The words in the set, all lowercase.
+    words: HashSet<String>
+}
+
+impl HashSetLexicon {
+    /// Creates a new lexicon with the given words, in lowercase.
+    pub fn new(words: Vec<String>) -> HashSetLexicon {
+        HashSetLexicon{words: words.iter().map(|word| word.to_lowercase()).collect()}
+    }
+}
+
+impl From<Vec<String>> for HashSetLexicon {
+    fn from(words: Vec<String>) -> Self {
+        HashSetLexicon::new(words)
+    }
+}
+
+impl From<VecLexicon> for HashSetLexicon {
+    fn from(lexicon: VecLexicon) -> Self {
+        // Case-preserving and case-sensitive lexicons iterate over words that
+        // aren't lowercase, so they still need to go through `new`.
+        HashSetLexicon::new(lexicon.into_iter().collect())
+    }
+}
+
+impl IntoIterator for HashSetLexicon {
+    type Item = String;
+    type IntoIter = std::collections::hash_set::IntoIter<Self::Item>;
+
+    /// Iterates over the words in an unspecified order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.words.into_iter()
+    }
+}
+
+impl Lexicon for HashSetLexicon {
+    /// Returns `true` if the set contains the given word and `false`
+    /// otherwise. The query is lowercased first, like the stored words.
+    fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+
+    fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    fn with_letter(&mut self, letter: char) {
+        let letter = letters::to_lowercase(letter);
+        self.words.retain(|word| word.contains(letter));
+    }
+
+    fn without_letter(&mut self, letter: char) {
+        let letter = letters::to_lowercase(letter);
+        self.words.retain(|word| !word.contains(letter));
+    }
+
+    fn only_using_letters<T: IntoIterator<Item = char>>(&mut self, letters: T) {
+        let string: String = letters.into_iter().map(letters::to_lowercase).collect();
+        self.words.retain(|word| word.chars().all(|l| string.contains(l)));
+    }
+
+    fn anagrams_of(&mut self, letters: &str) {
+        let available = letters::letter_counts(&letters.to_lowercase());
+        self.words.retain(|word| letters::fits_within(word, &available));
+    }
+
+    fn with_pattern(&mut self, pattern: &str) {
+        let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+        self.words.retain(|word| letters::matches_pattern(word, &pattern, '.'));
+    }
+
+    fn with_letter_at(&mut self, index: usize, letter: char) {
+        let letter = letters::to_lowercase(letter);
+        self.words.retain(|word| word.chars().nth(index) == Some(letter));
+    }
+
+    fn apply_wordle_guess(&mut self, guess: &str, feedback: &[Feedback]) {
+        let guess = guess.to_lowercase();
+        self.words.retain(|word| wordle::is_consistent(word, &guess, feedback));
+    }
+
+    fn starts_with(&mut self, prefix: &str) {
+        let prefix = prefix.to_lowercase();
+        self.words.retain(|word| word.starts_with(&prefix));
+    }
+
+    fn ends_with(&mut self, suffix: &str) {
+        let suffix = suffix.to_lowercase();
+        self.words.retain(|word| word.ends_with(&suffix));
+    }
+
+    fn containing(&mut self, substring: &str) {
+        let substring = substring.to_lowercase();
+        self.words.retain(|word| word.contains(&substring));
+    }
+
+    fn exclude_words<'a, I: IntoIterator<Item = &'a str>>(&mut self, words: I) {
+        for word in words {
+            self.words.remove(&word.to_lowercase());
+        }
+    }
+
+    fn retain_words<F: Fn(&str) -> bool>(&mut self, predicate: F) {
+        self.words.retain(|word| predicate(word));
+    }
+
+    fn with_exact_length(&mut self, length: usize) {
+        self.words.retain(|word| word.chars().count() == length);
+    }
+
+    fn with_more_length(&mut self, length: usize) {
+        self.words.retain(|word| word.chars().count() > length);
+    }
+
+    fn with_less_length(&mut self, length: usize) {
+        self.words.retain(|word| word.chars().count() < length);
+    }
+
+    fn with_length_in_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.words.retain(|word| range.contains(&word.chars().count()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lexicon(words: &[&str]) -> HashSetLexicon {
+        HashSetLexicon::new(words.iter().map(|&w| String::from(w)).collect())
+    }
+
+    #[test]
+    fn test_contains_ignores_case() {
+        let lex = lexicon(&["apple"]);
+        assert!(lex.contains("APPLE"));
+        assert!(lex.contains("Apple"));
+        assert!(lex.contains("apple"));
+        assert!(!lex.contains("app"));
+    }
+
+    #[test]
+    fn test_from_case_preserving() {
+        let lex = HashSetLexicon::from(VecLexicon::new_case_preserving(vec![String::from("McDonald")]));
+        assert!(lex.contains("mcdonald"));
+        assert!(lex.contains("McDonald"));
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut lex = lexicon(&["café", "naïve", "él", "test", "tea"]);
+        lex.with_exact_length(4);
+        assert!(lex.contains("café"));
+        assert!(lex.contains("test"));
+        assert!(!lex.contains("naïve"));
+
+        let mut lex = lexicon(&["café", "naïve", "él", "test", "tea"]);
+        lex.with_letter('t');
+        lex.without_letter('s');
+        assert_eq!(lex, lexicon(&["tea"]));
+    }
+
+    #[test]
+    fn test_from_vec_lexicon() {
+        let words = vec![String::from("Apple"), String::from("pear")];
+        let lex = HashSetLexicon::from(VecLexicon::new(words.clone()));
+        assert_eq!(lex, HashSetLexicon::new(words));
+        assert!(lex.contains("apple"));
+    }
+
+    #[test]
+    fn test_many_lookups() {
+        let lex = HashSetLexicon::new((0..1_000).map(|i| format!("Word{}", i)).collect());
+        let found = (0..10_000).filter(|i| lex.contains(&format!("word{}", i))).count();
+        assert_eq!(found, 1_000);
+        assert_eq!(lex.len(), 1_000);
+    }
+
+    #[test]
+    fn test_exclude_words() {
+        let mut lex = lexicon(&["apple", "cat", "dog", "pear"]);
+        lex.exclude_words(vec!["Apple", "cat", "zebra"]);
+        assert_eq!(lex, lexicon(&["dog", "pear"]));
+    }
+}
@@ -0,0 +1,151 @@
+//! Helpers for counting letters in words, shared by the different `Lexicon`
+//! implementations so that they agree on what each filter means.
+
+use std::collections::HashMap;
+
+use crate::lexicon::VowelPolicy;
+
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Returns the number of times each letter appears in the given word.
+pub(crate) fn letter_counts(word: &str) -> HashMap<char, usize> {
+    let mut counts = HashMap::new();
+    for letter in word.chars() {
+        *counts.entry(letter).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Returns `true` if the word can be spelled using each available letter at
+/// most as many times as it appears in `available`.
+pub(crate) fn fits_within(word: &str, available: &HashMap<char, usize>) -> bool {
+    letter_counts(word)
+        .iter()
+        .all(|(letter, count)| available.get(letter).is_some_and(|avail| count <= avail))
+}
+
+/// Like `fits_within`, but up to `blanks` letters of the word that aren't
+/// available may be covered by blank tiles instead.
+pub(crate) fn fits_within_with_blanks(word: &str, available: &HashMap<char, usize>,
+                                      blanks: usize) -> bool {
+    let missing: usize = letter_counts(word)
+        .iter()
+        .map(|(letter, &count)| count.saturating_sub(available.get(letter).copied().unwrap_or(0)))
+        .sum();
+    missing <= blanks
+}
+
+/// The bit that `ascii_mask` sets for words with characters other than the
+/// lowercase ASCII letters.
+pub(crate) const NOT_ASCII_LOWERCASE: u32 = 1 << 31;
+
+/// Returns the set of letters from 'a' to 'z' in the word as a bitmask, with
+/// bit 0 standing for 'a'. If the word has any other characters, the
+/// `NOT_ASCII_LOWERCASE` bit is set too, since the mask doesn't describe it
+/// completely.
+pub(crate) fn ascii_mask(word: &str) -> u32 {
+    word.chars().fold(0, |mask, letter| {
+        if letter.is_ascii_lowercase() {
+            mask | 1 << (letter as u32 - 'a' as u32)
+        } else {
+            mask | NOT_ASCII_LOWERCASE
+        }
+    })
+}
+
+/// Returns `true` if the word has each letter at least as many times as it
+/// appears in `required`.
+pub(crate) fn covers(word: &str, required: &HashMap<char, usize>) -> bool {
+    let counts = letter_counts(word);
+    required.iter().all(|(letter, count)| counts.get(letter).is_some_and(|have| have >= count))
+}
+
+/// Returns the word without any apostrophes, straight or curly, so "don't"
+/// becomes "dont".
+pub(crate) fn strip_apostrophes(word: &str) -> String {
+    word.chars().filter(|&l| l != '\'' && l != '\u{2019}').collect()
+}
+
+/// Returns the letter in lowercase. The few letters whose lowercase form is
+/// more than one `char`, like 'İ', keep only the first.
+pub(crate) fn to_lowercase(letter: char) -> char {
+    letter.to_lowercase().next().unwrap_or(letter)
+}
+
+/// Returns the number of vowels in the word, as given by the policy.
+pub(crate) fn vowel_count(word: &str, policy: VowelPolicy) -> usize {
+    word.chars().filter(|&l| policy.is_vowel(l)).count()
+}
+
+/// Returns the number of consonants in the word: the alphabetic characters
+/// that aren't vowels under the policy. Hyphens, apostrophes, and the like
+/// are neither vowels nor consonants.
+pub(crate) fn consonant_count(word: &str, policy: VowelPolicy) -> usize {
+    word.chars().filter(|&l| l.is_alphabetic() && !policy.is_vowel(l)).count()
+}
+
+/// Returns `true` if the letters of `short` appear in `long` in the same order,
+/// though not necessarily next to each other, like "pit" in "spirit".
+pub(crate) fn is_subsequence(short: &str, long: &str) -> bool {
+    let mut long = long.chars();
+    short.chars().all(|letter| long.any(|l| l == letter))
+}
+
+/// Returns `true` if the word has the same length as `pattern` and matches it
+/// position by position, with `wildcard` matching any single letter.
+pub(crate) fn matches_pattern(word: &str, pattern: &[char], wildcard: char) -> bool {
+    word.chars().count() == pattern.len()
+        && word.chars().zip(pattern).all(|(l, &p)| p == wildcard || l == p)
+}
+
+/// Returns `true` if the two words have the same length and differ at
+/// exactly one position, like "cat" and "cot".
+pub(crate) fn one_substitution_apart(a: &str, b: &str) -> bool {
+    a.chars().count() == b.chars().count()
+        && a.chars().zip(b.chars()).filter(|(x, y)| x != y).count() == 1
+}
+
+/// Returns `true` if the words are exactly one insertion, deletion, or
+/// substitution of a single letter apart, like "cat" and "cart".
+pub(crate) fn one_edit_apart(a: &str, b: &str) -> bool {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    match longer.len() - shorter.len() {
+        0 => shorter.iter().zip(&longer).filter(|(x, y)| x != y).count() == 1,
+        1 => {
+            // Skip the first letter that differs in the longer word, and the
+            // rest must line up.
+            let skip = shorter.iter().zip(&longer).take_while(|(x, y)| x == y).count();
+            shorter[skip..] == longer[skip + 1..]
+        }
+        _ => false,
+    }
+}
+
+/// Returns the Levenshtein distance between the words: the fewest single
+/// letter insertions, deletions, and substitutions that turn one into the
+/// other.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // The distances from the prefix of `a` seen so far to each prefix of `b`.
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &y) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(x != y);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Removes diacritics from the given word by decomposing each character and
+/// dropping the combining marks, so "naïve" becomes "naive". Letters that
+/// don't decompose, like 'ø' or 'ß', are left as they are.
+#[cfg(feature = "unicode-normalization")]
+pub(crate) fn fold_accents(word: &str) -> String {
+    word.nfd().filter(|&c| !is_combining_mark(c)).nfc().collect()
+}
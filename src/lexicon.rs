@@ -1,13 +1,63 @@
 //! `Lexicon` is the basic trait that powers the library, describing a set of words that can be
 //! filtered in various ways.
 
+use std::collections::HashSet;
+use std::ops::RangeBounds;
+
+use crate::letters;
+use crate::query::LexiconQuery;
+use crate::rules::{GameRules, RejectionReason};
+use crate::syllables;
+use crate::wordle::Feedback;
+
 /// Describes a set of strings that is queryable for specific criteria. Depending on the exact
 /// implementation, different operations will be faster or slower.
 pub trait Lexicon {
     /// Returns `true` if the lexicon contains the given value and `false` otherwise.
     fn contains(&self, word: &str) -> bool;
 
-    /// Keeps only the words in the `Lexicon` that have the given letter.
+    /// Returns `true` if the lexicon contains the given token from running
+    /// text, ignoring any characters other than letters at its start and end,
+    /// so "apple," and "(apple)" both match "apple". Apostrophes and hyphens
+    /// inside the token are kept, so "don't." only matches "don't".
+    fn contains_token(&self, token: &str) -> bool {
+        self.contains(token.trim_matches(|c: char| !c.is_alphabetic()))
+    }
+
+    /// Checks whether a player's word can be played under the given rules,
+    /// returning why not if it can't: the first rule it breaks, as given by
+    /// `GameRules::check`, or `RejectionReason::NotInDictionary` if it follows
+    /// the rules but isn't in the `Lexicon`.
+    fn validate(&self, word: &str, rules: &GameRules) -> Result<(), RejectionReason> {
+        rules.check(word)?;
+        if self.contains(word) { Ok(()) } else { Err(RejectionReason::NotInDictionary) }
+    }
+
+    /// Keeps only the words in the `Lexicon` that can be played under the
+    /// given rules, all in one call. The words kept are exactly the ones
+    /// `validate` accepts, so letters are compared ignoring case even in a
+    /// case-sensitive `Lexicon`.
+    fn apply_rules(&mut self, rules: &GameRules) {
+        self.retain_words(|word| rules.check(word).is_ok());
+    }
+
+    /// Returns the number of words remaining in the `Lexicon`.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if no words remain in the `Lexicon`.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Starts a chain of filters on a copy of the `Lexicon`, so the original
+    /// can be reused. See `LexiconQuery` for details.
+    fn query(&self) -> LexiconQuery<Self> where Self: Clone + Sized {
+        LexiconQuery::new(self.clone())
+    }
+
+    /// Keeps only the words in the `Lexicon` that have the given letter. Like
+    /// the other filters, this ignores the case of the letter unless the
+    /// `Lexicon` is case-sensitive.
     fn with_letter(&mut self, letter: char);
 
     /// Removes any words from the `Lexicon` that have the given letter.
@@ -19,14 +69,29 @@ pub trait Lexicon {
     fn only_using_letters<T: IntoIterator<Item = char>>(&mut self, letters: T);
 
     /// Keeps only the words in the `Lexicon` that have all of the given letters.
-    /// Implemented via chained `with_letter()` calls by default. Different from `only_using_letters`
-    /// in that, in this method, the returned words must contain all of the letters given.
+    /// Implemented via chained `with_letter()` calls by default, so a repeated letter only needs to
+    /// appear once; `VecLexicon` instead requires it as many times as it's given. Different from
+    /// `only_using_letters` in that, in this method, the returned words must contain all of the
+    /// letters given.
     fn with_letters<T: IntoIterator<Item = char>>(&mut self, letters: T) {
         for letter in letters {
             self.with_letter(letter);
         }
     }
 
+    /// Keeps only the words in the `Lexicon` that use every one of the given
+    /// letters and no others, like the pangrams of a Spelling Bee puzzle. For
+    /// "dog", both "god" and "good" are kept, but not "dogs" or "do". Repeats
+    /// in `letters` don't matter. Implemented via `only_using_letters` and
+    /// `with_letter` by default.
+    fn using_exactly_letters<T: IntoIterator<Item = char>>(&mut self, letters: T) {
+        let letters: HashSet<char> = letters.into_iter().collect();
+        self.only_using_letters(letters.iter().copied());
+        for &letter in &letters {
+            self.with_letter(letter);
+        }
+    }
+
     /// Removes all words in the `Lexicon` that have any of the given letters.
     /// Implemented via chained `without_letter()` calls by default.
     fn without_letters<T: IntoIterator<Item = char>>(&mut self, letters: T) {
@@ -35,18 +100,200 @@ pub trait Lexicon {
         }
     }
 
+    /// Keeps only the words in the `Lexicon` that can be spelled from the
+    /// given letters, using each letter at most as many times as it appears in
+    /// `letters`. With the rack "aet", for example, "ate" and "at" are kept
+    /// but "tee" is not. Different from `only_using_letters`, which allows
+    /// letters to be reused any number of times.
+    fn anagrams_of(&mut self, letters: &str);
+
+    /// Keeps only the words in the `Lexicon` that are formed solely from the
+    /// letters passed in, treating each occurrence of a letter as one use of
+    /// it. Unlike `only_using_letters`, "bee" is rejected if only one 'e' is
+    /// given. Implemented via `anagrams_of` by default.
+    fn only_using_letters_limited<T: IntoIterator<Item = char>>(&mut self, letters: T) {
+        let letters: String = letters.into_iter().collect();
+        self.anagrams_of(&letters);
+    }
+
+    /// Keeps only the words in the `Lexicon` written entirely in the given
+    /// alphabet, such as to pin a list to the letters of one language. Like
+    /// `only_using_letters`, but letters match in either case, and characters
+    /// that aren't letters at all, like hyphens and apostrophes, are allowed
+    /// anywhere. Implemented via `retain_words` by default.
+    fn restrict_to_alphabet(&mut self, alphabet: &str) {
+        let alphabet: HashSet<char> = alphabet
+            .chars()
+            .flat_map(|letter| letter.to_lowercase().chain(letter.to_uppercase()).chain(Some(letter)))
+            .collect();
+        self.retain_words(|word| word.chars().all(|l| !l.is_alphabetic() || alphabet.contains(&l)));
+    }
+
+    /// Keeps only the answers to a Spelling Bee puzzle: words at least
+    /// `min_length` letters long, spelled only from `letters` and the
+    /// `center` letter, with the center letter appearing at least once.
+    /// Letters may be reused, as in the game. This is the same as chaining
+    /// `only_using_letters`, `with_letter`, and `with_length_in_range`.
+    fn spelling_bee(&mut self, letters: &str, center: char, min_length: usize) {
+        self.only_using_letters(letters.chars().chain(std::iter::once(center)));
+        self.with_letter(center);
+        self.with_length_in_range(min_length..);
+    }
+
+    /// Keeps only the words in the `Lexicon` that can be spelled from the
+    /// given letters plus `blanks` wildcard tiles, as in Scrabble: like
+    /// `anagrams_of`, but up to `blanks` letters of each word may be ones that
+    /// `letters` doesn't have enough of. With "ca" and one blank, "cat" and
+    /// "car" are kept but "cart" is not. Implemented via `retain_words` by
+    /// default.
+    fn anagrams_of_with_blanks(&mut self, letters: &str, blanks: usize) {
+        let available = letters::letter_counts(&letters.to_lowercase());
+        self.retain_words(|word| letters::fits_within_with_blanks(word, &available, blanks));
+    }
+
+    /// Keeps only the words in the `Lexicon` that are exact anagrams of the
+    /// given word, using every one of its letters exactly as often, so for
+    /// "listen" both "silent" and "tinsel" are kept but "lent" isn't. The word
+    /// itself is kept if it's in the `Lexicon`: remove it with
+    /// `exclude_words` if only rearrangements are wanted. Implemented via
+    /// `retain_words` by default.
+    fn exact_anagrams(&mut self, word: &str) {
+        let target = letters::letter_counts(&word.to_lowercase());
+        self.retain_words(|candidate| letters::letter_counts(candidate) == target);
+    }
+
+    /// Keeps only the words in the `Lexicon` that match the given pattern, in
+    /// which `.` matches any single letter and every other character must
+    /// appear at that exact position. Words must have the same length as the
+    /// pattern, counted as in `with_exact_length`. For example, "cra.e"
+    /// matches "crane" and "crate" but not "cranes".
+    fn with_pattern(&mut self, pattern: &str);
+
+    /// Keeps only the words in the `Lexicon` that have the given letter at
+    /// the given 0-based position, counted in `char`s. Words too short to
+    /// have that position are removed.
+    fn with_letter_at(&mut self, index: usize, letter: char);
+
+    /// Keeps only the words in the `Lexicon` that could be the answer to a
+    /// Wordle game in which `guess` received the given feedback, one entry
+    /// per letter. Duplicate letters are handled as in the game itself: see
+    /// the `wordle` module for details.
+    fn apply_wordle_guess(&mut self, guess: &str, feedback: &[Feedback]);
+
+    /// Keeps only the words in the `Lexicon` that begin with the given prefix.
+    /// The prefix itself counts as a match if it's a word, so combine this
+    /// with `with_more_length` to require letters after the prefix. An empty
+    /// prefix keeps every word.
+    fn starts_with(&mut self, prefix: &str);
+
+    /// Keeps only the words in the `Lexicon` that end with the given suffix,
+    /// with the same caveats as `starts_with`. Words shorter than the suffix
+    /// are removed.
+    fn ends_with(&mut self, suffix: &str);
+
+    /// Keeps only the words in the `Lexicon` in which the given substring
+    /// appears contiguously, anywhere in the word. Different from
+    /// `with_letters`, which allows the letters to appear in any order and
+    /// position. An empty substring keeps every word.
+    fn containing(&mut self, substring: &str);
+
+    /// Removes all words in the `Lexicon` in which any of the given substrings
+    /// appears, ignoring case, such as "qu" for a puzzle without a Q tile. The
+    /// opposite of `containing`, for several substrings at once. An empty
+    /// substring appears in every word, so it removes them all. Implemented
+    /// via `retain_words` by default.
+    fn without_substrings<'a, I: IntoIterator<Item = &'a str>>(&mut self, substrings: I) {
+        let substrings: Vec<String> = substrings.into_iter().map(str::to_lowercase).collect();
+        self.retain_words(|word| !substrings.iter().any(|sub| word.contains(sub.as_str())));
+    }
+
+    /// Removes each of the given words from the `Lexicon`, ignoring case like
+    /// `contains`. Words that aren't in the `Lexicon` are ignored.
+    fn exclude_words<'a, I: IntoIterator<Item = &'a str>>(&mut self, words: I);
+
+    /// Keeps only the words in the `Lexicon` for which the given predicate
+    /// returns `true`, for filters that aren't built in, like "no two vowels
+    /// in a row". The predicate sees each word as it's stored, so in
+    /// lowercase for the case-insensitive lexicons.
+    fn retain_words<F: Fn(&str) -> bool>(&mut self, predicate: F);
+
     /// Keeps only the words in the `Lexicon` that have exactly the given
-    /// length. Note that the exact interpretation of this can vary for some
-    /// Unicode strings.
+    /// length. Length is measured in Unicode scalar values (`char`s), not
+    /// bytes or grapheme clusters, so "café" has length 4 but a decomposed
+    /// "cafe\u{301}" has length 5.
     fn with_exact_length(&mut self, length: usize);
 
     /// Keeps only the words in the `Lexicon` that are longer than the given
-    /// length. Note that the exact interpretation of this can vary for some
-    /// Unicode strings.
+    /// length, counted in `char`s as in `with_exact_length`.
     fn with_more_length(&mut self, length: usize);
 
     /// Keeps only the words in the `Lexicon` that have less than the given
-    /// length. Note that the exact interpretation of this can vary for some
-    /// Unicode strings.
+    /// length, counted in `char`s as in `with_exact_length`.
     fn with_less_length(&mut self, length: usize);
+
+    /// Keeps only the words in the `Lexicon` whose length falls in the given
+    /// range, counted in `char`s as in `with_exact_length`. Any kind of range
+    /// works, so `3..=7`, `..5`, and `2..` are all valid.
+    fn with_length_in_range<R: RangeBounds<usize>>(&mut self, range: R);
+
+    /// Keeps only the words in the `Lexicon` with exactly the given number of
+    /// vowels, counting each of a, e, i, o, and u every time it appears, so
+    /// "queue" has 4. 'y' is never counted as a vowel, even in words like
+    /// "rhythm": use `with_vowel_count_using` to count it.
+    fn with_vowel_count(&mut self, count: usize) {
+        self.with_vowel_count_using(count, VowelPolicy::AeiouOnly);
+    }
+
+    /// Keeps only the words in the `Lexicon` with exactly the given number of
+    /// vowels, where the policy decides whether 'y' is one. Implemented via
+    /// `retain_words` by default.
+    fn with_vowel_count_using(&mut self, count: usize, policy: VowelPolicy) {
+        self.retain_words(|word| letters::vowel_count(word, policy) == count);
+    }
+
+    /// Keeps only the words in the `Lexicon` with exactly the given number of
+    /// consonants, meaning letters other than the vowels of
+    /// `with_vowel_count`, so 'y' is always a consonant. Characters that
+    /// aren't letters, like hyphens, don't count.
+    fn with_consonant_count(&mut self, count: usize) {
+        self.with_consonant_count_using(count, VowelPolicy::AeiouOnly);
+    }
+
+    /// Keeps only the words in the `Lexicon` with exactly the given number of
+    /// consonants, where the policy decides whether 'y' is one. Implemented
+    /// via `retain_words` by default.
+    fn with_consonant_count_using(&mut self, count: usize, policy: VowelPolicy) {
+        self.retain_words(|word| letters::consonant_count(word, policy) == count);
+    }
+
+    /// Keeps only the words in the `Lexicon` with the given number of
+    /// syllables, as estimated by `syllables::estimate_syllables`. The
+    /// estimate is a heuristic, so expect some words to be miscounted.
+    /// Implemented via `retain_words` by default.
+    fn with_syllable_count(&mut self, count: usize) {
+        self.retain_words(|word| syllables::estimate_syllables(word) == count);
+    }
+}
+
+/// Which letters the vowel-counting filters treat as vowels. Whether 'y' is
+/// a vowel depends on the word and on the game, so it's left to the caller.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum VowelPolicy {
+    /// Only a, e, i, o, and u are vowels, so "rhythm" has none.
+    #[default]
+    AeiouOnly,
+    /// 'y' is a vowel as well, wherever it appears, so "yes" has two.
+    AeiouY,
+}
+
+impl VowelPolicy {
+    /// Returns `true` if the letter is a vowel under this policy, in either
+    /// case.
+    pub fn is_vowel(self, letter: char) -> bool {
+        match letter.to_ascii_lowercase() {
+            'a' | 'e' | 'i' | 'o' | 'u' => true,
+            'y' => self == VowelPolicy::AeiouY,
+            _ => false,
+        }
+    }
 }
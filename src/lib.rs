@@ -1,24 +1,56 @@
-#[macro_use]
-extern crate lazy_static;
-
+pub mod dawg;
+pub mod error;
+pub mod hashsetlexicon;
+mod letters;
 pub mod lexicon;
+pub mod query;
+pub mod rules;
+pub mod scoring;
+pub mod syllables;
+pub mod trie;
 pub mod veclexicon;
+pub mod weightedlexicon;
+pub mod wordle;
 pub mod wordlist;
 
-pub use lexicon::Lexicon;
-pub use veclexicon::VecLexicon;
+pub use dawg::DawgLexicon;
+pub use error::LexiError;
+pub use hashsetlexicon::HashSetLexicon;
+pub use lexicon::{Lexicon, VowelPolicy};
+pub use query::LexiconQuery;
+pub use rules::{GameRules, RejectionReason};
+pub use trie::TrieLexicon;
+pub use veclexicon::{LazyQuery, VecLexicon};
+pub use weightedlexicon::WeightedLexicon;
+pub use wordle::Feedback;
+pub use wordlist::Flag;
+
+pub const MAIN_WORDLIST_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/2of12inf.txt");
+pub const SWEARS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/swears.txt");
+
+/// The contents of the default main word list, as found at `MAIN_WORDLIST_PATH`.
+#[cfg(feature = "bundled-wordlist")]
+pub const MAIN_WORDLIST: &str = include_str!("../2of12inf.txt");
+/// The contents of the default swear list, as found at `SWEARS_PATH`.
+#[cfg(feature = "bundled-wordlist")]
+pub const SWEARS: &str = include_str!("../swears.txt");
 
-pub const MAIN_WORDLIST_PATH: &'static str = "../lexi/2of12inf.txt";
-pub const SWEARS_PATH: &'static str = "../lexi/swears.txt";
+#[cfg(feature = "bundled-wordlist")]
+lazy_static::lazy_static! {
+    /// The standard wordlist for word games, derived from the `2of12inf` list
+    /// from [`12dicts`](http://wordlist.aspell.net/12dicts-readme/#2of12inf).
+    /// Plurals of uncountable nouns (e.g., "acnes") are removed, as are swears.
+    /// Neologisms are kept.
+    pub static ref WORDLIST: VecLexicon =
+        wordlist::parse_strings(MAIN_WORDLIST, SWEARS).unwrap().into();
+}
 
-// lazy_static! {
-//     /// The standard wordlist for word games, derived from the `2of12inf` list
-//     /// from [`12dicts`](http://wordlist.aspell.net/12dicts-readme/#2of12inf).
-//     /// Plurals of uncountable nouns (e.g., "acnes") are removed, as are swears.
-//     /// Neologisms are kept.
-//     pub static ref WORDLIST: veclexicon::VecLexicon =
-//         wordlist::parse_list(MAIN_WORDLIST_PATH, SWEARS_PATH).unwrap().into();
-// }
+/// Returns a copy of `WORDLIST`, ready to be filtered. The list is embedded in
+/// the library, so this works without any files at runtime.
+#[cfg(feature = "bundled-wordlist")]
+pub fn default_lexicon() -> VecLexicon {
+    WORDLIST.clone()
+}
 
 #[cfg(test)]
 mod tests {
@@ -26,8 +58,10 @@ mod tests {
     use super::veclexicon::VecLexicon;
     use super::wordlist::Flag;
     use super::lexicon::Lexicon;
+    use super::trie::TrieLexicon;
+    use super::dawg::DawgLexicon;
 
-    fn gen_lexicon(flags: Vec<Flag>) -> VecLexicon {
+    fn gen_lexicon(flags: &[Flag]) -> VecLexicon {
         let list = wordlist::parse_list(MAIN_WORDLIST_PATH, SWEARS_PATH).unwrap();
         list.custom_list(flags).into()
     }
@@ -46,7 +80,7 @@ mod tests {
         assert!(!wl1.contains("acnes"));
         assert!(!wl1.contains("asdkflj"));
 
-        let wl2 = gen_lexicon(vec![Flag::UncountablePlurals, Flag::Swears]);
+        let wl2 = gen_lexicon(&[Flag::UncountablePlurals, Flag::Swears]);
         assert!(wl2.contains("apple"));
         assert!(wl2.contains("fuck"));
         assert!(!wl2.contains("blogger"));
@@ -54,17 +88,170 @@ mod tests {
         assert!(!wl2.contains("asdkflj"));
     }
 
+    #[test]
+    #[cfg(feature = "bundled-wordlist")]
+    fn test_default_lexicon() {
+        let lex = default_lexicon();
+        assert!(lex.contains("apple"));
+        assert!(lex.contains("blogger"));
+        assert!(!lex.contains("fuck"));
+        assert!(!lex.contains("acnes"));
+        assert_eq!(lex, gen_default_lexicon());
+    }
+
     #[test]
     fn test_spelling_bee_like() {
         let mut lex = gen_default_lexicon();
         lex.only_using_letters("doughby".chars());
         lex.with_letter('o');
         lex.with_more_length(3);
-        for word in lex {
-            println!("{}", word);
-        }
+
+        let mut with_all = lex.clone();
+        with_all.with_letters("doughby".chars());
+        assert!(!lex.is_empty());
+        assert_eq!(lex.pangrams("doughby"), with_all.into_iter().collect::<Vec<String>>());
+    }
+
+    #[test]
+    fn test_spelling_bee_matches_chain() {
+        let mut chained = gen_default_lexicon();
+        chained.only_using_letters("doughby".chars());
+        chained.with_letter('o');
+        chained.with_more_length(3);
+
+        let mut lex = gen_default_lexicon();
+        lex.spelling_bee("dughby", 'o', 4);
+        assert!(!lex.is_empty());
+        assert_eq!(lex, chained);
+
+        let mut trie = TrieLexicon::new(gen_default_lexicon().into_iter().collect());
+        trie.spelling_bee("doughby", 'o', 4);
+        assert_eq!(trie.len(), chained.len());
     }
     
+    #[test]
+    fn test_trie_matches_vec() {
+        let words = wordlist::parse_list(MAIN_WORDLIST_PATH, SWEARS_PATH).unwrap().default_list();
+        let mut vec_lex = VecLexicon::new(words.clone());
+        let mut trie_lex = TrieLexicon::new(words);
+        for word in &["apple", "Blogger", "acnes", "asdkflj"] {
+            assert_eq!(vec_lex.contains(word), trie_lex.contains(word));
+        }
+
+        vec_lex.only_using_letters("doughby".chars());
+        vec_lex.with_letter('o');
+        vec_lex.with_more_length(3);
+        trie_lex.only_using_letters("doughby".chars());
+        trie_lex.with_letter('o');
+        trie_lex.with_more_length(3);
+
+        let mut vec_words: Vec<String> = vec_lex.into_iter().collect();
+        vec_words.sort();
+        let trie_words: Vec<String> = trie_lex.into_iter().collect();
+        assert!(!trie_words.is_empty());
+        assert_eq!(vec_words, trie_words);
+    }
+
+    #[test]
+    fn test_dawg_matches_vec() {
+        let words = wordlist::parse_list(MAIN_WORDLIST_PATH, SWEARS_PATH).unwrap().default_list();
+        let vec_lex = VecLexicon::new(words.clone());
+        let dawg_lex = DawgLexicon::new(words.clone());
+        assert_eq!(dawg_lex.len(), vec_lex.len());
+
+        // Every word, plus near misses that are sometimes also words.
+        let mut candidates: Vec<String> = vec![];
+        for word in &words {
+            candidates.push(word.clone());
+            candidates.push(format!("{}s", word));
+            candidates.push(word.chars().skip(1).collect());
+        }
+        let candidates: Vec<&str> = candidates.iter().map(String::as_str).collect();
+        let from_dawg: Vec<&str> = candidates.iter().copied().filter(|w| dawg_lex.contains(w)).collect();
+        assert_eq!(vec_lex.filter_valid(&candidates), from_dawg);
+
+        let mut sorted = words;
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(dawg_lex.into_iter().collect::<Vec<String>>(), sorted);
+    }
+
+    #[test]
+    fn test_retain_words_matches_across_lexicons() {
+        let words = wordlist::parse_list(MAIN_WORDLIST_PATH, SWEARS_PATH).unwrap().default_list();
+        let same_ends = |word: &str| word.len() > 1 && word.chars().next() == word.chars().last();
+
+        let mut vec_lex = VecLexicon::new(words.clone());
+        vec_lex.retain_words(same_ends);
+        assert!(vec_lex.contains("level"));
+        assert!(!vec_lex.contains("apple"));
+        let mut expected: Vec<String> = vec_lex.into_iter().collect();
+        expected.sort();
+
+        let mut trie_lex = TrieLexicon::new(words.clone());
+        trie_lex.retain_words(same_ends);
+        assert_eq!(trie_lex.into_iter().collect::<Vec<String>>(), expected);
+
+        let mut dawg_lex = DawgLexicon::new(words.clone());
+        dawg_lex.retain_words(same_ends);
+        assert_eq!(dawg_lex.into_iter().collect::<Vec<String>>(), expected);
+
+        let mut hash_lex = HashSetLexicon::new(words);
+        hash_lex.retain_words(same_ends);
+        assert_eq!(hash_lex.len(), expected.len());
+    }
+
+    /// Checks that the letter filters of a lexicon of "dog", "good", "cat",
+    /// and "Doug" ignore the case of the letters they're given.
+    fn check_letter_filters_ignore_case<L: Lexicon + Clone + PartialEq + std::fmt::Debug>(lex: L) {
+        let filters: [fn(&mut L, bool); 5] = [
+            |lex, upper| lex.with_letter(if upper { 'O' } else { 'o' }),
+            |lex, upper| lex.without_letter(if upper { 'G' } else { 'g' }),
+            |lex, upper| lex.only_using_letters(if upper { "DOG" } else { "dog" }.chars()),
+            |lex, upper| lex.with_letters(if upper { "GO" } else { "go" }.chars()),
+            |lex, upper| lex.with_letter_at(0, if upper { 'D' } else { 'd' }),
+        ];
+        for filter in &filters {
+            let mut lower = lex.clone();
+            filter(&mut lower, false);
+            let mut upper = lex.clone();
+            filter(&mut upper, true);
+            assert!(lower.len() > 0 && lower.len() < lex.len());
+            assert_eq!(lower, upper);
+        }
+    }
+
+    #[test]
+    fn test_letter_filters_ignore_case() {
+        let words: Vec<String> = ["dog", "good", "cat", "Doug"].iter().map(|&w| String::from(w)).collect();
+        check_letter_filters_ignore_case(VecLexicon::new(words.clone()));
+        check_letter_filters_ignore_case(TrieLexicon::new(words.clone()));
+        check_letter_filters_ignore_case(DawgLexicon::new(words.clone()));
+        check_letter_filters_ignore_case(HashSetLexicon::new(words.clone()));
+
+        let lex = gen_default_lexicon();
+        let upper: Vec<&str> = lex.lazy().with_letter('Q').only_using_letters("QUEST".chars()).into_iter().collect();
+        let lower: Vec<&str> = lex.lazy().with_letter('q').only_using_letters("quest".chars()).into_iter().collect();
+        assert!(!lower.is_empty());
+        assert_eq!(upper, lower);
+
+        let mut case_sensitive = VecLexicon::new_case_sensitive(words);
+        case_sensitive.with_letter('D');
+        assert_eq!(case_sensitive.into_iter().collect::<Vec<String>>(), vec!["Doug"]);
+    }
+
+    #[test]
+    fn test_masked_only_using_letters_matches_scan() {
+        let lex = gen_default_lexicon();
+        for letters in &["doughby", "aeiourst", "etaoinshrdlu", "q", "", "abcdefghijklmnopqrstuvwxyz"] {
+            let mut masked = lex.clone();
+            masked.only_using_letters(letters.chars());
+            let mut scanned = lex.clone();
+            scanned.retain_words(|word| word.chars().all(|l| letters.contains(l)));
+            assert_eq!(masked, scanned, "{}", letters);
+        }
+    }
+
     #[test]
     fn it_works() {
         assert_eq!(2 + 2, 4);
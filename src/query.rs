@@ -0,0 +1,272 @@
+//! A builder for chaining `Lexicon` filters in a single expression, like
+//! `lex.query().with_letter('a').with_less_length(6).collect()`. Each query
+//! works on its own copy of the lexicon, so the original can be queried again
+//! afterwards.
+
+use std::ops::RangeBounds;
+
+use crate::lexicon::{Lexicon, VowelPolicy};
+use crate::rules::GameRules;
+use crate::wordle::Feedback;
+
+/// A lexicon being filtered by a chain of builder calls. Each method applies
+/// the `Lexicon` method of the same name and returns the query for further
+/// chaining. Use `apply` for filters that aren't part of the trait.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LexiconQuery<L> {
+    /// The lexicon with all of the filters so far applied.
+    lexicon: L,
+}
+
+impl<L: Lexicon> LexiconQuery<L> {
+    /// Starts a query on the given lexicon, which is filtered in place.
+    pub fn new(lexicon: L) -> LexiconQuery<L> {
+        LexiconQuery{lexicon}
+    }
+
+    /// Applies an arbitrary filter to the lexicon, such as one of the
+    /// filters specific to a single `Lexicon` implementation.
+    pub fn apply<F: FnOnce(&mut L)>(mut self, filter: F) -> Self {
+        filter(&mut self.lexicon);
+        self
+    }
+
+    /// Finishes the query, returning the filtered lexicon.
+    pub fn into_lexicon(self) -> L {
+        self.lexicon
+    }
+
+    /// Keeps only the words playable under the rules, as in `Lexicon::apply_rules`.
+    pub fn apply_rules(mut self, rules: &GameRules) -> Self {
+        self.lexicon.apply_rules(rules);
+        self
+    }
+
+    /// Keeps only the words with the letter, as in `Lexicon::with_letter`.
+    pub fn with_letter(mut self, letter: char) -> Self {
+        self.lexicon.with_letter(letter);
+        self
+    }
+
+    /// Removes the words with the letter, as in `Lexicon::without_letter`.
+    pub fn without_letter(mut self, letter: char) -> Self {
+        self.lexicon.without_letter(letter);
+        self
+    }
+
+    /// Keeps only the words spelled solely from the letters, as in `Lexicon::only_using_letters`.
+    pub fn only_using_letters<T: IntoIterator<Item = char>>(mut self, letters: T) -> Self {
+        self.lexicon.only_using_letters(letters);
+        self
+    }
+
+    /// Keeps only the words with all of the letters, as in `Lexicon::with_letters`.
+    pub fn with_letters<T: IntoIterator<Item = char>>(mut self, letters: T) -> Self {
+        self.lexicon.with_letters(letters);
+        self
+    }
+
+    /// Keeps only the words using exactly the letters, as in `Lexicon::using_exactly_letters`.
+    pub fn using_exactly_letters<T: IntoIterator<Item = char>>(mut self, letters: T) -> Self {
+        self.lexicon.using_exactly_letters(letters);
+        self
+    }
+
+    /// Removes the words with any of the letters, as in `Lexicon::without_letters`.
+    pub fn without_letters<T: IntoIterator<Item = char>>(mut self, letters: T) -> Self {
+        self.lexicon.without_letters(letters);
+        self
+    }
+
+    /// Keeps only the words spelled from the rack, as in `Lexicon::anagrams_of`.
+    pub fn anagrams_of(mut self, letters: &str) -> Self {
+        self.lexicon.anagrams_of(letters);
+        self
+    }
+
+    /// Keeps only the words spelled from the letters once each,
+    /// as in `Lexicon::only_using_letters_limited`.
+    pub fn only_using_letters_limited<T: IntoIterator<Item = char>>(mut self, letters: T) -> Self {
+        self.lexicon.only_using_letters_limited(letters);
+        self
+    }
+
+    /// Keeps only the words written in the alphabet, as in `Lexicon::restrict_to_alphabet`.
+    pub fn restrict_to_alphabet(mut self, alphabet: &str) -> Self {
+        self.lexicon.restrict_to_alphabet(alphabet);
+        self
+    }
+
+    /// Keeps only the answers to the Spelling Bee puzzle, as in `Lexicon::spelling_bee`.
+    pub fn spelling_bee(mut self, letters: &str, center: char, min_length: usize) -> Self {
+        self.lexicon.spelling_bee(letters, center, min_length);
+        self
+    }
+
+    /// Keeps only the words spelled from the rack and blanks,
+    /// as in `Lexicon::anagrams_of_with_blanks`.
+    pub fn anagrams_of_with_blanks(mut self, letters: &str, blanks: usize) -> Self {
+        self.lexicon.anagrams_of_with_blanks(letters, blanks);
+        self
+    }
+
+    /// Keeps only the exact anagrams of the word, as in `Lexicon::exact_anagrams`.
+    pub fn exact_anagrams(mut self, word: &str) -> Self {
+        self.lexicon.exact_anagrams(word);
+        self
+    }
+
+    /// Keeps only the words matching the pattern, as in `Lexicon::with_pattern`.
+    pub fn with_pattern(mut self, pattern: &str) -> Self {
+        self.lexicon.with_pattern(pattern);
+        self
+    }
+
+    /// Keeps only the words with the letter at the position, as in `Lexicon::with_letter_at`.
+    pub fn with_letter_at(mut self, index: usize, letter: char) -> Self {
+        self.lexicon.with_letter_at(index, letter);
+        self
+    }
+
+    /// Keeps only the words consistent with the Wordle feedback,
+    /// as in `Lexicon::apply_wordle_guess`.
+    pub fn apply_wordle_guess(mut self, guess: &str, feedback: &[Feedback]) -> Self {
+        self.lexicon.apply_wordle_guess(guess, feedback);
+        self
+    }
+
+    /// Keeps only the words beginning with the prefix, as in `Lexicon::starts_with`.
+    pub fn starts_with(mut self, prefix: &str) -> Self {
+        self.lexicon.starts_with(prefix);
+        self
+    }
+
+    /// Keeps only the words ending with the suffix, as in `Lexicon::ends_with`.
+    pub fn ends_with(mut self, suffix: &str) -> Self {
+        self.lexicon.ends_with(suffix);
+        self
+    }
+
+    /// Keeps only the words containing the substring, as in `Lexicon::containing`.
+    pub fn containing(mut self, substring: &str) -> Self {
+        self.lexicon.containing(substring);
+        self
+    }
+
+    /// Removes the words containing any of the substrings, as in `Lexicon::without_substrings`.
+    pub fn without_substrings<'a, I: IntoIterator<Item = &'a str>>(mut self, substrings: I) -> Self {
+        self.lexicon.without_substrings(substrings);
+        self
+    }
+
+    /// Removes the given words, as in `Lexicon::exclude_words`.
+    pub fn exclude_words<'a, I: IntoIterator<Item = &'a str>>(mut self, words: I) -> Self {
+        self.lexicon.exclude_words(words);
+        self
+    }
+
+    /// Keeps only the words the predicate accepts, as in `Lexicon::retain_words`.
+    pub fn retain_words<F: Fn(&str) -> bool>(mut self, predicate: F) -> Self {
+        self.lexicon.retain_words(predicate);
+        self
+    }
+
+    /// Keeps only the words of exactly the length, as in `Lexicon::with_exact_length`.
+    pub fn with_exact_length(mut self, length: usize) -> Self {
+        self.lexicon.with_exact_length(length);
+        self
+    }
+
+    /// Keeps only the words longer than the length, as in `Lexicon::with_more_length`.
+    pub fn with_more_length(mut self, length: usize) -> Self {
+        self.lexicon.with_more_length(length);
+        self
+    }
+
+    /// Keeps only the words shorter than the length, as in `Lexicon::with_less_length`.
+    pub fn with_less_length(mut self, length: usize) -> Self {
+        self.lexicon.with_less_length(length);
+        self
+    }
+
+    /// Keeps only the words with lengths in the range, as in `Lexicon::with_length_in_range`.
+    pub fn with_length_in_range<R: RangeBounds<usize>>(mut self, range: R) -> Self {
+        self.lexicon.with_length_in_range(range);
+        self
+    }
+
+    /// Keeps only the words with that many vowels, as in `Lexicon::with_vowel_count`.
+    pub fn with_vowel_count(mut self, count: usize) -> Self {
+        self.lexicon.with_vowel_count(count);
+        self
+    }
+
+    /// Keeps only the words with that many vowels, as in `Lexicon::with_vowel_count_using`.
+    pub fn with_vowel_count_using(mut self, count: usize, policy: VowelPolicy) -> Self {
+        self.lexicon.with_vowel_count_using(count, policy);
+        self
+    }
+
+    /// Keeps only the words with that many consonants, as in `Lexicon::with_consonant_count`.
+    pub fn with_consonant_count(mut self, count: usize) -> Self {
+        self.lexicon.with_consonant_count(count);
+        self
+    }
+
+    /// Keeps only the words with that many consonants, as in `Lexicon::with_consonant_count_using`.
+    pub fn with_consonant_count_using(mut self, count: usize, policy: VowelPolicy) -> Self {
+        self.lexicon.with_consonant_count_using(count, policy);
+        self
+    }
+
+    /// Keeps only the words with that many syllables, as in `Lexicon::with_syllable_count`.
+    pub fn with_syllable_count(mut self, count: usize) -> Self {
+        self.lexicon.with_syllable_count(count);
+        self
+    }
+}
+
+impl<L: Lexicon + IntoIterator<Item = String>> LexiconQuery<L> {
+    /// Finishes the query, returning the words that passed every filter in
+    /// the lexicon's iteration order.
+    pub fn collect(self) -> Vec<String> {
+        self.lexicon.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trie::TrieLexicon;
+    use crate::veclexicon::VecLexicon;
+
+    use super::*;
+
+    fn words() -> Vec<String> {
+        ["apple", "banana", "cat", "apricot", "plum", "grape"].iter().map(|&w| String::from(w)).collect()
+    }
+
+    #[test]
+    fn test_query_matches_chained_filters() {
+        let lex = VecLexicon::new(words());
+        let queried = lex.query().with_letter('a').with_less_length(6).collect();
+
+        let mut chained = lex.clone();
+        chained.with_letter('a');
+        chained.with_less_length(6);
+        assert_eq!(queried, chained.into_iter().collect::<Vec<String>>());
+        assert_eq!(queried, vec!["apple", "cat", "grape"]);
+
+        // The original lexicon is untouched.
+        assert_eq!(lex.len(), 6);
+    }
+
+    #[test]
+    fn test_query_apply() {
+        let lex = TrieLexicon::new(words());
+        let queried = lex.query()
+            .starts_with("ap")
+            .apply(|lex| lex.without_letter('l'))
+            .into_lexicon();
+        assert_eq!(queried, TrieLexicon::new(vec![String::from("apricot")]));
+    }
+}
@@ -0,0 +1,192 @@
+//! The rules a word game places on the words that can be played, gathered in
+//! one place so that a game can check a player's word with
+//! `Lexicon::validate` and say exactly why it was turned down, or find every
+//! playable word at once with `Lexicon::apply_rules`.
+
+use std::error::Error;
+use std::fmt;
+
+/// The constraints on playable words. Every field is optional and allows
+/// anything by default, so set only the ones a game needs:
+///
+/// ```
+/// use lexi::GameRules;
+///
+/// let spelling_bee = GameRules{
+///     min_length: Some(4),
+///     allowed_letters: Some(String::from("doughby")),
+///     required_letters: String::from("o"),
+///     ..GameRules::default()
+/// };
+/// assert!(spelling_bee.check("doubt").is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct GameRules {
+    /// The fewest letters a word can have, if any.
+    pub min_length: Option<usize>,
+    /// The most letters a word can have, if any.
+    pub max_length: Option<usize>,
+    /// The only letters words can use, each any number of times, if the game
+    /// restricts them.
+    pub allowed_letters: Option<String>,
+    /// Letters every word must use at least once.
+    pub required_letters: String,
+    /// Letters no word can use.
+    pub forbidden_letters: String,
+}
+
+/// Why a word was turned down by `Lexicon::validate`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RejectionReason {
+    /// The word has fewer letters than the minimum given.
+    TooShort {
+        /// The fewest letters allowed.
+        min: usize,
+    },
+    /// The word has more letters than the maximum given.
+    TooLong {
+        /// The most letters allowed.
+        max: usize,
+    },
+    /// The word uses a letter the rules don't allow.
+    DisallowedLetter(char),
+    /// The word doesn't use a letter the rules require.
+    MissingLetter(char),
+    /// The word follows the rules, but isn't in the lexicon.
+    NotInDictionary,
+}
+
+impl GameRules {
+    /// Checks the word against the rules alone, without looking it up, and
+    /// returns the first rule it breaks. Lengths are counted in `char`s, and
+    /// letters are compared ignoring case. The lengths are checked first, then
+    /// the letters the word uses in the order they appear, and then the
+    /// required letters. A forbidden letter is a `DisallowedLetter` too.
+    pub fn check(&self, word: &str) -> Result<(), RejectionReason> {
+        let word = word.to_lowercase();
+        let length = word.chars().count();
+        if let Some(min) = self.min_length.filter(|&min| length < min) {
+            return Err(RejectionReason::TooShort{min});
+        }
+        if let Some(max) = self.max_length.filter(|&max| length > max) {
+            return Err(RejectionReason::TooLong{max});
+        }
+        if let Some(allowed) = &self.allowed_letters {
+            let allowed = allowed.to_lowercase();
+            if let Some(letter) = word.chars().find(|&l| !allowed.contains(l)) {
+                return Err(RejectionReason::DisallowedLetter(letter));
+            }
+        }
+        let forbidden = self.forbidden_letters.to_lowercase();
+        if let Some(letter) = word.chars().find(|&l| forbidden.contains(l)) {
+            return Err(RejectionReason::DisallowedLetter(letter));
+        }
+        if let Some(letter) = self.required_letters.to_lowercase().chars().find(|&l| !word.contains(l)) {
+            return Err(RejectionReason::MissingLetter(letter));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectionReason::TooShort{min} => write!(f, "words must have at least {} letters", min),
+            RejectionReason::TooLong{max} => write!(f, "words can have at most {} letters", max),
+            RejectionReason::DisallowedLetter(letter) => write!(f, "'{}' isn't allowed", letter),
+            RejectionReason::MissingLetter(letter) => write!(f, "words must use '{}'", letter),
+            RejectionReason::NotInDictionary => write!(f, "not in the word list"),
+        }
+    }
+}
+
+impl Error for RejectionReason {}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexicon::Lexicon;
+    use crate::veclexicon::VecLexicon;
+
+    use super::*;
+
+    fn rules() -> GameRules {
+        GameRules{
+            min_length: Some(4),
+            max_length: Some(6),
+            allowed_letters: Some(String::from("doughby")),
+            required_letters: String::from("o"),
+            forbidden_letters: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let lex = VecLexicon::new(["good", "hood", "dough", "doughboy", "bud", "dugh"]
+            .iter()
+            .map(|&w| String::from(w))
+            .collect());
+        let rules = rules();
+        assert_eq!(lex.validate("good", &rules), Ok(()));
+        assert_eq!(lex.validate("DOUGH", &rules), Ok(()));
+        assert_eq!(lex.validate("bod", &rules), Err(RejectionReason::TooShort{min: 4}));
+        assert_eq!(lex.validate("doughboy", &rules), Err(RejectionReason::TooLong{max: 6}));
+        assert_eq!(lex.validate("goods", &rules), Err(RejectionReason::DisallowedLetter('s')));
+        assert_eq!(lex.validate("dugh", &rules), Err(RejectionReason::MissingLetter('o')));
+        assert_eq!(lex.validate("boyhood", &GameRules::default()), Err(RejectionReason::NotInDictionary));
+        assert_eq!(lex.validate("bud", &GameRules::default()), Ok(()));
+    }
+
+    #[test]
+    fn test_apply_rules_matches_chain() {
+        let rules = GameRules{
+            min_length: Some(4),
+            max_length: Some(7),
+            allowed_letters: Some(String::from("DOUGHBY")),
+            required_letters: String::from("og"),
+            forbidden_letters: String::from("y"),
+        };
+        let full: VecLexicon = crate::wordlist::parse_list(crate::MAIN_WORDLIST_PATH, crate::SWEARS_PATH)
+            .unwrap()
+            .into();
+
+        let mut lex = full.clone();
+        lex.apply_rules(&rules);
+        let mut chained = full.clone();
+        chained.only_using_letters("doughby".chars());
+        chained.with_letter('o');
+        chained.with_letter('g');
+        chained.without_letter('y');
+        chained.with_length_in_range(4..=7);
+        assert!(!lex.is_empty());
+        assert_eq!(lex, chained);
+
+        // The words kept are exactly the ones that pass validation.
+        let valid: Vec<&str> = full.iter().filter(|word| full.validate(word, &rules).is_ok()).collect();
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), valid);
+        assert_eq!(full.validate("goodby", &rules), Err(RejectionReason::DisallowedLetter('y')));
+
+        let mut unchanged = full.clone();
+        unchanged.apply_rules(&GameRules::default());
+        assert_eq!(unchanged, full);
+    }
+
+    #[test]
+    fn test_apply_rules_case_sensitive() {
+        let words = ["Polish", "polish", "NASA"].iter().map(|&w| String::from(w)).collect();
+        let full = VecLexicon::new_case_sensitive(words);
+        let rules = GameRules{allowed_letters: Some(String::from("POLISH")), ..GameRules::default()};
+        assert_eq!(full.validate("Polish", &rules), Ok(()));
+
+        let mut lex = full.clone();
+        lex.apply_rules(&rules);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["Polish", "polish"]);
+        let valid: Vec<&str> = full.iter().filter(|word| full.validate(word, &rules).is_ok()).collect();
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), valid);
+    }
+
+    #[test]
+    fn test_rejection_messages() {
+        assert_eq!(RejectionReason::TooShort{min: 4}.to_string(), "words must have at least 4 letters");
+        assert_eq!(RejectionReason::DisallowedLetter('s').to_string(), "'s' isn't allowed");
+    }
+}
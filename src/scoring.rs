@@ -0,0 +1,74 @@
+//! Scores for words under the rules of various word games.
+
+/// Returns the value of a single tile in English-language Scrabble. Letters
+/// are case-insensitive, and anything that isn't an ASCII letter (including
+/// blanks) is worth zero.
+pub fn scrabble_letter_score(letter: char) -> u32 {
+    match letter.to_ascii_lowercase() {
+        'a' | 'e' | 'i' | 'l' | 'n' | 'o' | 'r' | 's' | 't' | 'u' => 1,
+        'd' | 'g' => 2,
+        'b' | 'c' | 'm' | 'p' => 3,
+        'f' | 'h' | 'v' | 'w' | 'y' => 4,
+        'k' => 5,
+        'j' | 'x' => 8,
+        'q' | 'z' => 10,
+        _ => 0,
+    }
+}
+
+/// Returns the total face value of the tiles spelling the given word in
+/// English-language Scrabble, without any board bonuses.
+pub fn scrabble_score(word: &str) -> u32 {
+    word.chars().map(scrabble_letter_score).sum()
+}
+
+/// The bonus for a Spelling Bee pangram, which uses every letter of the
+/// puzzle.
+const PANGRAM_BONUS: u32 = 7;
+
+/// Returns the points a word earns in the New York Times Spelling Bee for the
+/// puzzle with the given letters: 1 for a four-letter word, one per letter for
+/// longer words, and 7 more for a pangram. Words shorter than four letters
+/// aren't allowed, so they're worth zero. The word is assumed to be spelled
+/// from `letters`; both are compared ignoring case.
+pub fn spelling_bee_score(word: &str, letters: &str) -> u32 {
+    let word = word.to_lowercase();
+    let length = word.chars().count() as u32;
+    let base = match length {
+        0..=3 => return 0,
+        4 => 1,
+        _ => length,
+    };
+    let is_pangram = letters.to_lowercase().chars().all(|l| word.contains(l));
+    if is_pangram { base + PANGRAM_BONUS } else { base }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrabble_score() {
+        assert_eq!(scrabble_score("quiz"), 22);
+        assert_eq!(scrabble_score("area"), 4);
+        assert!(scrabble_score("quiz") > scrabble_score("area"));
+        assert_eq!(scrabble_score("QUIZ"), 22);
+    }
+
+    #[test]
+    fn test_spelling_bee_score() {
+        assert_eq!(spelling_bee_score("good", "doughby"), 1);
+        assert_eq!(spelling_bee_score("hoodoo", "doughby"), 6);
+        assert_eq!(spelling_bee_score("doughboy", "doughby"), 7 + 8);
+        assert_eq!(spelling_bee_score("DOUGHBOY", "doughby"), 15);
+        assert_eq!(spelling_bee_score("dog", "doughby"), 0);
+    }
+
+    #[test]
+    fn test_non_letters_score_zero() {
+        assert_eq!(scrabble_score(""), 0);
+        assert_eq!(scrabble_score("c?t"), 4);
+        assert_eq!(scrabble_score("can't"), 6);
+        assert_eq!(scrabble_score("café"), 8);
+    }
+}
@@ -0,0 +1,56 @@
+//! Rough syllable counts for English words, for tools like haiku checkers.
+//! English spelling is far too irregular for this to be exact without a
+//! pronouncing dictionary, so the estimate is a heuristic that's usually right
+//! for common words and off by one for many others.
+
+/// Estimates the number of syllables in an English word by counting groups of
+/// consecutive vowels, treating 'y' as a vowel except at the start of the
+/// word. A trailing silent 'e', as in "make", doesn't count, but the "le" of
+/// words like "apple" does. Every non-empty word has at least one syllable.
+pub fn estimate_syllables(word: &str) -> usize {
+    let letters: Vec<char> = word.to_lowercase().chars().filter(|l| l.is_alphabetic()).collect();
+    if letters.is_empty() {
+        return 0;
+    }
+
+    let is_vowel = |i: usize| match letters[i] {
+        'a' | 'e' | 'i' | 'o' | 'u' => true,
+        'y' => i > 0,
+        _ => false,
+    };
+    let mut count = (0..letters.len()).filter(|&i| is_vowel(i) && (i == 0 || !is_vowel(i - 1))).count();
+
+    let n = letters.len();
+    let silent_e = n >= 2 && letters[n - 1] == 'e' && !is_vowel(n - 2);
+    let consonant_le = n >= 3 && letters[n - 2] == 'l' && !is_vowel(n - 3);
+    if count > 1 && silent_e && !consonant_le {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_syllables() {
+        assert_eq!(estimate_syllables("apple"), 2);
+        assert_eq!(estimate_syllables("cat"), 1);
+        assert_eq!(estimate_syllables("beautiful"), 3);
+        assert_eq!(estimate_syllables("make"), 1);
+        assert_eq!(estimate_syllables("queue"), 1);
+        assert_eq!(estimate_syllables("rhythm"), 1);
+        assert_eq!(estimate_syllables("yes"), 1);
+        assert_eq!(estimate_syllables("Banana"), 3);
+        assert_eq!(estimate_syllables(""), 0);
+    }
+
+    #[test]
+    fn test_estimate_is_close() {
+        // Words the heuristic gets wrong, but only by one.
+        for &(word, syllables) in &[("idea", 3), ("fire", 2), ("create", 2), ("business", 2)] {
+            assert!(estimate_syllables(word).abs_diff(syllables) <= 1, "{}", word);
+        }
+    }
+}
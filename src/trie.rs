@@ -0,0 +1,454 @@
+//! Implements `Lexicon` with a prefix trie, with one node per letter of each
+//! stored prefix. This gives O(k) performance for `contains`, where k is the
+//! length of the queried word, at the cost of more memory than a `Vec`. The
+//! filtering operations are still O(n) in the size of the lexicon: they walk
+//! every word once, unmarking words that fail the filter and pruning branches
+//! that no longer lead to any word. Each node also keeps the number of words
+//! below it, so `len` and `count_with_prefix` don't need to walk the trie.
+//!
+//! Like `VecLexicon`, this lexicon is case-insensitive, and converts everything
+//! to lowercase internally.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ops::RangeBounds;
+
+use crate::letters;
+use crate::lexicon::Lexicon;
+use crate::wordle::{self, Feedback};
+
+/// A single node in the trie, representing the prefix spelled by the path from
+/// the root to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+struct TrieNode {
+    /// The nodes for each letter that can follow this prefix.
+    children: BTreeMap<char, TrieNode>,
+    /// Whether this prefix is itself a word in the lexicon.
+    is_word: bool,
+    /// The number of words at or below this node, including this one.
+    count: usize,
+}
+
+impl TrieNode {
+    /// Returns the node for the given prefix, if any word starts with it.
+    fn find(&self, prefix: &str) -> Option<&TrieNode> {
+        let mut node = self;
+        for letter in prefix.chars() {
+            node = node.children.get(&letter)?;
+        }
+        Some(node)
+    }
+
+    /// Pushes the words at or below this node onto `words`, in alphabetical
+    /// order, stopping once `words` holds `limit` words. `prefix` must hold
+    /// the prefix this node represents.
+    fn collect_words(&self, prefix: &mut String, words: &mut Vec<String>, limit: usize) {
+        if words.len() >= limit {
+            return;
+        }
+        if self.is_word {
+            words.push(prefix.clone());
+        }
+        for (&letter, child) in &self.children {
+            prefix.push(letter);
+            child.collect_words(prefix, words, limit);
+            prefix.pop();
+        }
+    }
+
+    /// Removes every word at or below this node for which `keep` returns
+    /// `false`, along with any branches left without words, and updates the
+    /// counts to match. Returns `true` if there are still words at or below
+    /// this node.
+    fn retain<F: Fn(&str) -> bool>(&mut self, prefix: &mut String, keep: &F) -> bool {
+        if self.is_word && !keep(prefix) {
+            self.is_word = false;
+        }
+        self.children.retain(|&letter, child| {
+            prefix.push(letter);
+            let nonempty = child.retain(prefix, keep);
+            prefix.pop();
+            nonempty
+        });
+        self.count = self.children.values().map(|child| child.count).sum::<usize>()
+            + usize::from(self.is_word);
+        self.count > 0
+    }
+}
+
+/// A list of words stored as a prefix trie.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TrieLexicon {
+    /// The node for the empty prefix.
+    root: TrieNode,
+}
+
+impl TrieLexicon {
+    /// Creates a new lexicon with the given words, in lowercase.
+    pub fn new(words: Vec<String>) -> TrieLexicon {
+        let mut lexicon = TrieLexicon::default();
+        for word in words {
+            lexicon.insert(&word.to_lowercase());
+        }
+        lexicon
+    }
+
+    /// Adds a single word, which should already be lowercase.
+    fn insert(&mut self, word: &str) {
+        if self.root.find(word).is_some_and(|node| node.is_word) {
+            return;
+        }
+        let mut node = &mut self.root;
+        node.count += 1;
+        for letter in word.chars() {
+            node = node.children.entry(letter).or_default();
+            node.count += 1;
+        }
+        node.is_word = true;
+    }
+
+    /// Returns all of the words in the lexicon, in alphabetical order.
+    fn words(&self) -> Vec<String> {
+        let mut words = vec![];
+        self.root.collect_words(&mut String::new(), &mut words, usize::MAX);
+        words
+    }
+
+    /// Keeps only the words for which `keep` returns `true`.
+    fn retain<F: Fn(&str) -> bool>(&mut self, keep: F) {
+        self.root.retain(&mut String::new(), &keep);
+    }
+
+    /// Returns `true` if any word in the lexicon starts with the given prefix,
+    /// ignoring case. This is O(k) in the length of the prefix.
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        self.count_with_prefix(prefix) > 0
+    }
+
+    /// Returns the number of words starting with the given prefix, ignoring
+    /// case, such as to decide which branches of a search to try first. The
+    /// prefix counts if it's a word itself. This is O(k) in the length of the
+    /// prefix, since each node keeps count of the words below it.
+    pub fn count_with_prefix(&self, prefix: &str) -> usize {
+        self.root.find(&prefix.to_lowercase()).map_or(0, |node| node.count)
+    }
+
+    /// Returns up to `limit` words starting with the given prefix, ignoring
+    /// case, in alphabetical order, as for autocompletion. Only the part of the
+    /// trie below the prefix is visited, and the search stops once `limit`
+    /// words are found.
+    pub fn completions(&self, prefix: &str, limit: usize) -> Vec<String> {
+        let mut prefix = prefix.to_lowercase();
+        let mut words = vec![];
+        if let Some(node) = self.root.find(&prefix) {
+            node.collect_words(&mut prefix, &mut words, limit);
+        }
+        words
+    }
+
+    /// Returns every word in the lexicon that can be spelled on the given
+    /// square Boggle board by moving between adjacent tiles, including
+    /// diagonally, without using any tile twice. A `q` tile stands for the
+    /// "Qu" tile and spells both letters. The words are returned in
+    /// alphabetical order, without duplicates. There's no minimum length, so
+    /// call `with_more_length(2)` first to get the usual Boggle rules.
+    ///
+    /// The search stops following a path as soon as no word starts with the
+    /// letters along it, so this is much faster than checking every path.
+    pub fn solve_boggle<const N: usize>(&self, board: &[[char; N]; N]) -> Vec<String> {
+        let board: Vec<Vec<char>> = board
+            .iter()
+            .map(|row| row.iter().flat_map(|tile| tile.to_lowercase()).collect())
+            .collect();
+        let mut visited = [[false; N]; N];
+        let mut found = BTreeSet::new();
+        for row in 0..N {
+            for col in 0..N {
+                boggle_search(&self.root, &board, &mut visited, (row, col),
+                              &mut String::new(), &mut found);
+            }
+        }
+        found.into_iter().collect()
+    }
+}
+
+/// Extends the Boggle path ending at `node`, whose letters are in `prefix`,
+/// onto the tile at `pos` and everything reachable from it, adding any words
+/// spelled along the way to `found`.
+fn boggle_search<const N: usize>(node: &TrieNode, board: &[Vec<char>], visited: &mut [[bool; N]; N],
+                                 pos: (usize, usize), prefix: &mut String,
+                                 found: &mut BTreeSet<String>) {
+    let (row, col) = pos;
+    let tile: &[char] = if board[row][col] == 'q' { &['q', 'u'] } else { &board[row][col..=col] };
+    let node = match tile.iter().try_fold(node, |node, letter| node.children.get(letter)) {
+        Some(node) => node,
+        None => return,
+    };
+
+    prefix.extend(tile);
+    visited[row][col] = true;
+    if node.is_word {
+        found.insert(prefix.clone());
+    }
+    for next_row in row.saturating_sub(1)..=(row + 1).min(N - 1) {
+        for next_col in col.saturating_sub(1)..=(col + 1).min(N - 1) {
+            if !visited[next_row][next_col] {
+                boggle_search(node, board, visited, (next_row, next_col), prefix, found);
+            }
+        }
+    }
+    visited[row][col] = false;
+    for _ in tile {
+        prefix.pop();
+    }
+}
+
+impl From<Vec<String>> for TrieLexicon {
+    fn from(words: Vec<String>) -> Self {
+        TrieLexicon::new(words)
+    }
+}
+
+impl IntoIterator for TrieLexicon {
+    type Item = String;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    /// Iterates over the words in alphabetical order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.words().into_iter()
+    }
+}
+
+impl Lexicon for TrieLexicon {
+    /// Returns `true` if the trie contains the given word and `false`
+    /// otherwise. The query is lowercased first, like the stored words.
+    fn contains(&self, word: &str) -> bool {
+        self.root.find(&word.to_lowercase()).is_some_and(|node| node.is_word)
+    }
+
+    fn len(&self) -> usize {
+        self.root.count
+    }
+
+    fn with_letter(&mut self, letter: char) {
+        let letter = letters::to_lowercase(letter);
+        self.retain(|word| word.contains(letter));
+    }
+
+    fn without_letter(&mut self, letter: char) {
+        let letter = letters::to_lowercase(letter);
+        self.retain(|word| !word.contains(letter));
+    }
+
+    fn only_using_letters<T: IntoIterator<Item = char>>(&mut self, letters: T) {
+        let string: String = letters.into_iter().map(letters::to_lowercase).collect();
+        self.retain(|word| word.chars().all(|l| string.contains(l)));
+    }
+
+    fn anagrams_of(&mut self, letters: &str) {
+        let available = letters::letter_counts(&letters.to_lowercase());
+        self.retain(|word| letters::fits_within(word, &available));
+    }
+
+    fn with_pattern(&mut self, pattern: &str) {
+        let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+        self.retain(|word| letters::matches_pattern(word, &pattern, '.'));
+    }
+
+    fn with_letter_at(&mut self, index: usize, letter: char) {
+        let letter = letters::to_lowercase(letter);
+        self.retain(|word| word.chars().nth(index) == Some(letter));
+    }
+
+    fn apply_wordle_guess(&mut self, guess: &str, feedback: &[Feedback]) {
+        let guess = guess.to_lowercase();
+        self.retain(|word| wordle::is_consistent(word, &guess, feedback));
+    }
+
+    /// Keeps only the words that begin with the given prefix by cutting out
+    /// the prefix's subtree, without visiting any other words.
+    fn starts_with(&mut self, prefix: &str) {
+        let prefix = prefix.to_lowercase();
+        let mut subtree = std::mem::take(&mut self.root);
+        for letter in prefix.chars() {
+            subtree = match subtree.children.remove(&letter) {
+                Some(child) => child,
+                None => return,
+            };
+        }
+        for letter in prefix.chars().rev() {
+            let mut parent = TrieNode{count: subtree.count, ..TrieNode::default()};
+            parent.children.insert(letter, subtree);
+            subtree = parent;
+        }
+        self.root = subtree;
+    }
+
+    fn ends_with(&mut self, suffix: &str) {
+        let suffix = suffix.to_lowercase();
+        self.retain(|word| word.ends_with(&suffix));
+    }
+
+    fn containing(&mut self, substring: &str) {
+        let substring = substring.to_lowercase();
+        self.retain(|word| word.contains(&substring));
+    }
+
+    fn exclude_words<'a, I: IntoIterator<Item = &'a str>>(&mut self, words: I) {
+        let excluded: HashSet<String> = words.into_iter().map(str::to_lowercase).collect();
+        self.retain(|word| !excluded.contains(word));
+    }
+
+    fn retain_words<F: Fn(&str) -> bool>(&mut self, predicate: F) {
+        self.retain(predicate);
+    }
+
+    fn with_exact_length(&mut self, length: usize) {
+        self.retain(|word| word.chars().count() == length);
+    }
+
+    fn with_more_length(&mut self, length: usize) {
+        self.retain(|word| word.chars().count() > length);
+    }
+
+    fn with_less_length(&mut self, length: usize) {
+        self.retain(|word| word.chars().count() < length);
+    }
+
+    fn with_length_in_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.retain(|word| range.contains(&word.chars().count()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lexicon(words: &[&str]) -> TrieLexicon {
+        TrieLexicon::new(words.iter().map(|&w| String::from(w)).collect())
+    }
+
+    #[test]
+    fn test_length_counts_chars() {
+        let mut lex = lexicon(&["café", "naïve", "él", "test"]);
+        lex.with_exact_length(4);
+        assert!(lex.contains("café"));
+        assert!(lex.contains("test"));
+        assert!(!lex.contains("naïve"));
+        assert!(!lex.contains("él"));
+
+        let mut lex = lexicon(&["café", "naïve", "él", "test"]);
+        lex.with_more_length(4);
+        assert!(lex.contains("naïve"));
+        assert!(!lex.contains("café"));
+
+        let mut lex = lexicon(&["café", "naïve", "él", "test"]);
+        lex.with_less_length(4);
+        assert!(lex.contains("él"));
+        assert!(!lex.contains("café"));
+    }
+
+    #[test]
+    fn test_contains_ignores_case() {
+        let lex = lexicon(&["apple"]);
+        assert!(lex.contains("APPLE"));
+        assert!(lex.contains("Apple"));
+        assert!(lex.contains("apple"));
+        assert!(!lex.contains("app"));
+        assert!(!lex.contains("apples"));
+    }
+
+    #[test]
+    fn test_filters_prune_prefixes() {
+        let mut lex = lexicon(&["a", "at", "ate", "bat"]);
+        lex.without_letter('t');
+        assert!(lex.contains("a"));
+        assert!(!lex.contains("at"));
+        assert!(!lex.contains("ate"));
+        assert!(lex.root.find("b").is_none());
+        assert_eq!(lex.len(), 1);
+        assert_eq!(lex.into_iter().collect::<Vec<String>>(), vec!["a"]);
+    }
+
+    #[test]
+    fn test_anagrams_of() {
+        let mut lex = lexicon(&["ate", "eat", "tea", "at", "eta", "tee", "bat", "teat"]);
+        lex.anagrams_of("AET");
+        assert_eq!(lex, lexicon(&["ate", "eat", "tea", "at", "eta"]));
+    }
+
+    #[test]
+    fn test_starts_and_ends_with() {
+        let mut lex = lexicon(&["pre", "prefix", "press", "repress", "apple"]);
+        lex.starts_with("");
+        assert_eq!(lex, lexicon(&["pre", "prefix", "press", "repress", "apple"]));
+        lex.starts_with("PRE");
+        assert_eq!(lex, lexicon(&["pre", "prefix", "press"]));
+
+        let mut lex = lexicon(&["sing", "ring", "ing", "in", "bringing", "rang"]);
+        lex.ends_with("ing");
+        assert_eq!(lex, lexicon(&["sing", "ring", "ing", "bringing"]));
+        lex.ends_with("ringing");
+        assert_eq!(lex, lexicon(&["bringing"]));
+
+        let mut lex = lexicon(&["pre", "prefix"]);
+        lex.starts_with("post");
+        assert_eq!(lex, TrieLexicon::default());
+    }
+
+    #[test]
+    fn test_solve_boggle() {
+        let lex = lexicon(&["cat", "cats", "eat", "tea", "rat", "ore", "act", "tat", "tree", "zzzzz"]);
+        let board = [['c', 'a', 't', 's'],
+                     ['x', 'e', 'r', 'z'],
+                     ['z', 'z', 'o', 'z'],
+                     ['z', 'z', 'z', 'z']];
+        assert_eq!(lex.solve_boggle(&board), vec!["cat", "cats", "eat", "ore", "rat", "tea", "zzzzz"]);
+    }
+
+    #[test]
+    fn test_solve_boggle_qu() {
+        let lex = lexicon(&["quit", "qit", "quite", "suit"]);
+        let board = [['Q', 'i', 'x'],
+                     ['x', 't', 'e'],
+                     ['x', 'x', 'x']];
+        assert_eq!(lex.solve_boggle(&board), vec!["quit", "quite"]);
+    }
+
+    #[test]
+    fn test_completions() {
+        let lex = lexicon(&["apple", "apply", "applesauce", "apt", "banana"]);
+        assert!(lex.has_prefix("app"));
+        assert!(lex.has_prefix("APP"));
+        assert!(lex.has_prefix("apple"));
+        assert!(!lex.has_prefix("apz"));
+        assert!(lex.has_prefix(""));
+        assert!(!TrieLexicon::default().has_prefix(""));
+
+        assert_eq!(lex.completions("app", 10), vec!["apple", "applesauce", "apply"]);
+        assert_eq!(lex.completions("App", 2), vec!["apple", "applesauce"]);
+        assert!(lex.completions("c", 10).is_empty());
+        assert!(lex.completions("a", 0).is_empty());
+    }
+
+    #[test]
+    fn test_count_with_prefix() {
+        let words = ["apple", "apply", "applesauce", "apt", "app", "banana", "band", "APPLE"];
+        let brute = |lex: &TrieLexicon, prefix: &str| {
+            lex.clone().into_iter().filter(|word| word.starts_with(prefix)).count()
+        };
+
+        let mut lex = lexicon(&words);
+        assert_eq!(lex.len(), 7);
+        for prefix in &["", "a", "ap", "app", "apple", "apples", "b", "band", "c", "applesauces"] {
+            assert_eq!(lex.count_with_prefix(prefix), brute(&lex, prefix), "{}", prefix);
+        }
+        assert_eq!(lex.count_with_prefix("APP"), 4);
+
+        lex.without_letter('y');
+        lex.starts_with("ap");
+        assert_eq!(lex.len(), 4);
+        for prefix in &["", "a", "ap", "app", "apple", "b"] {
+            assert_eq!(lex.count_with_prefix(prefix), brute(&lex, prefix), "{}", prefix);
+        }
+    }
+}
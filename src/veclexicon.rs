@@ -3,21 +3,858 @@
 //! trait, where n is the size of the lexicon and the size of words is
 //! negligible compared to the size of the lexicon.
 //!
-//! This lexicon is case-insensitive, and converts everything to lowercase internally.
+//! This lexicon is case-insensitive, and converts everything to lowercase
+//! internally, unless it's created with `VecLexicon::new_case_sensitive`. To
+//! keep matching case-insensitively but get back words like "McDonald" as they
+//! were given, use `VecLexicon::new_case_preserving`.
+//! With the `rayon` feature, the built-in filters check words in parallel,
+//! which can help when chaining many filters over a large list.
+//!
+//! It never holds the same word twice: duplicates are dropped as words are
+//! added, keeping the first copy of each. Otherwise, words stay in the order
+//! they were added: filters only ever remove words, with or without `rayon`,
+//! and only the sorting methods reorder them.
+//! With the `unicode-normalization` feature, it can also ignore accents: see
+//! `VecLexicon::new_normalized`. Similarly, `VecLexicon::ignore_non_alpha`
+//! makes the letter filters skip hyphens and apostrophes, while
+//! `VecLexicon::strip_apostrophes` removes apostrophes from the words
+//! altogether, as Scrabble does.
+//!
+//! To speed up `only_using_letters`, each word's letters are also stored as a
+//! bitmask, so that a word made of the letters a to z can be checked with a
+//! single integer operation. Other words are checked letter by letter.
+//!
+//! Each filter makes a full pass over the words. To chain several filters over
+//! a large list without that, use `VecLexicon::lazy`, which checks every filter
+//! on each word in a single pass as the results are iterated.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::iter::FromIterator;
+use std::ops::{Bound, RangeBounds};
+use std::path::Path;
 
+#[cfg(feature = "rand")]
+use rand::{seq::SliceRandom, Rng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::letters;
 use crate::lexicon::Lexicon;
+use crate::scoring;
+use crate::trie::TrieLexicon;
+use crate::wordle::{self, Feedback};
 
 /// A simple list of words.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize),
+           serde(from = "SerializedVecLexicon"))]
 pub struct VecLexicon {
-    /// The words in the list, all lowercase.
-    words: Vec<String>
+    /// The words in the list, all normalized and without duplicates.
+    words: Vec<String>,
+    /// Whether diacritics are stripped from words and queries.
+    #[cfg_attr(not(feature = "unicode-normalization"), allow(dead_code))]
+    fold_accents: bool,
+    /// Whether words and queries keep their case instead of being lowercased.
+    case_sensitive: bool,
+    /// Whether letter-based filters skip characters that aren't alphabetic.
+    ignore_non_alpha: bool,
+    /// Whether apostrophes are removed from words and queries.
+    strip_apostrophes: bool,
+    /// The mask given by `letters::ascii_mask` for each word, in the same
+    /// order as `words`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    masks: Vec<u32>,
+    /// The words as they were given, in the same order as `words`, if the
+    /// lexicon was created with `new_case_preserving`.
+    originals: Option<Vec<String>>,
 }
 
 impl VecLexicon {
-    /// Creates a new lexicon with the given words, in lowercase.
+    /// Creates a new lexicon with the given words, in lowercase. Duplicates,
+    /// including words differing only in case, are removed, leaving the rest
+    /// in their original order.
     pub fn new(words: Vec<String>) -> VecLexicon {
-        VecLexicon{words}
+        VecLexicon::default().with_new_words(words)
+    }
+
+    /// Creates a new lexicon with the given words, in lowercase and with
+    /// diacritics removed. Queries and filters have their diacritics removed
+    /// in the same way, so `contains("naive")` and `contains("naïve")` both
+    /// match a stored "naïve", and iteration yields "naive". Requires the
+    /// `unicode-normalization` feature.
+    #[cfg(feature = "unicode-normalization")]
+    pub fn new_normalized(words: Vec<String>) -> VecLexicon {
+        VecLexicon{fold_accents: true, ..VecLexicon::default()}.with_new_words(words)
+    }
+
+    /// Creates a new lexicon with the given words, keeping their case. Queries
+    /// and filters are case-sensitive too, so a lexicon holding "Polish"
+    /// doesn't contain "polish". Duplicates are still removed, but words
+    /// differing only in case are kept apart.
+    pub fn new_case_sensitive(words: Vec<String>) -> VecLexicon {
+        VecLexicon{case_sensitive: true, ..VecLexicon::default()}.with_new_words(words)
+    }
+
+    /// Creates a new lexicon with the given words, which are matched ignoring
+    /// case like those of `new`, but keep their original case when iterated
+    /// over: a lexicon of "McDonald" contains "mcdonald", and `iter` yields
+    /// "McDonald". Where words differ only in case, the first is kept. Other
+    /// methods that return words, like `pangrams`, also give them in their
+    /// original case.
+    pub fn new_case_preserving(words: Vec<String>) -> VecLexicon {
+        VecLexicon{originals: Some(vec![]), ..VecLexicon::default()}.with_new_words(words)
+    }
+
+    /// Adds the given words to this empty lexicon, normalizing them according
+    /// to its settings.
+    fn with_new_words<I: IntoIterator<Item = String>>(mut self, words: I) -> VecLexicon {
+        self.extend_words(words);
+        self
+    }
+
+    /// Adds a word, which must already be normalized and not be in the
+    /// lexicon, to the end of the list. `original` is the word as it was
+    /// given, before normalization, which keeps its case but still loses its
+    /// apostrophes if they're being stripped.
+    fn append(&mut self, word: String, original: &str) {
+        if let Some(originals) = &mut self.originals {
+            if self.strip_apostrophes {
+                originals.push(letters::strip_apostrophes(original));
+            } else {
+                originals.push(original.to_string());
+            }
+        }
+        self.masks.push(letters::ascii_mask(&word));
+        self.words.push(word);
+    }
+
+    /// Rearranges the words so that the word at `order[i]` moves to position
+    /// `i`. `order` must be a permutation of the positions of the words.
+    fn reorder(&mut self, order: &[usize]) {
+        self.words = order.iter().map(|&i| std::mem::take(&mut self.words[i])).collect();
+        self.masks = order.iter().map(|&i| self.masks[i]).collect();
+        if let Some(originals) = &mut self.originals {
+            *originals = order.iter().map(|&i| std::mem::take(&mut originals[i])).collect();
+        }
+    }
+
+    /// Sorts the words by the given comparison, which sees them normalized.
+    fn sort_words_by<F: Fn(&str, &str) -> Ordering>(&mut self, compare: F) {
+        let mut order: Vec<usize> = (0..self.words.len()).collect();
+        order.sort_unstable_by(|&a, &b| compare(&self.words[a], &self.words[b]));
+        self.reorder(&order);
+    }
+
+    /// Keeps only the words for which `keep` returns `true`, checking them in
+    /// parallel if the `rayon` feature is enabled. Either way, the remaining
+    /// words keep their order.
+    fn retain<F: Fn(&str) -> bool + Send + Sync>(&mut self, keep: F) {
+        self.retain_masked(|word, _| keep(word));
+    }
+
+    /// Like `retain`, but `keep` also gets the mask of each word.
+    fn retain_masked<F: Fn(&str, u32) -> bool + Send + Sync>(&mut self, keep: F) {
+        #[cfg(feature = "rayon")]
+        let kept: Vec<bool> = self.words
+            .par_iter()
+            .zip(self.masks.par_iter())
+            .map(|(word, &mask)| keep(word, mask))
+            .collect();
+        #[cfg(not(feature = "rayon"))]
+        let kept: Vec<bool> = self.words
+            .iter()
+            .zip(&self.masks)
+            .map(|(word, &mask)| keep(word, mask))
+            .collect();
+        self.retain_kept(&kept);
+    }
+
+    /// Like `retain`, but always checks the words one at a time, so `keep`
+    /// doesn't need to be shareable between threads.
+    fn retain_serial<F: Fn(&str) -> bool>(&mut self, keep: F) {
+        let kept: Vec<bool> = self.words.iter().map(|word| keep(word)).collect();
+        self.retain_kept(&kept);
+    }
+
+    /// Keeps the words, along with their masks and originals, at the
+    /// positions where `kept` is `true`. If fewer than a quarter of the words
+    /// the lists have room for are left, the spare memory is freed.
+    fn retain_kept(&mut self, kept: &[bool]) {
+        let mut flags = kept.iter();
+        self.words.retain(|_| *flags.next().unwrap());
+        let mut flags = kept.iter();
+        self.masks.retain(|_| *flags.next().unwrap());
+        if let Some(originals) = &mut self.originals {
+            let mut flags = kept.iter();
+            originals.retain(|_| *flags.next().unwrap());
+        }
+        if self.words.len() < self.words.capacity() / 4 {
+            self.shrink();
+        }
+    }
+
+    /// Frees any memory held for words that have since been filtered out.
+    /// The filters do this on their own once most of the words are gone, so
+    /// this is only needed after removing fewer words than that.
+    pub fn shrink(&mut self) {
+        self.words.shrink_to_fit();
+        self.masks.shrink_to_fit();
+        if let Some(originals) = &mut self.originals {
+            originals.shrink_to_fit();
+        }
+    }
+
+    /// Converts a word or query into the form the words are stored in.
+    pub(crate) fn normalize(&self, word: &str) -> String {
+        let word = if self.case_sensitive { word.to_string() } else { word.to_lowercase() };
+        let word = if self.strip_apostrophes { letters::strip_apostrophes(&word) } else { word };
+        #[cfg(feature = "unicode-normalization")]
+        if self.fold_accents {
+            return letters::fold_accents(&word);
+        }
+        word
+    }
+
+    /// Converts a single letter into the form the words are stored in, like
+    /// `normalize` does for whole words: lowercased unless the lexicon is
+    /// case-sensitive, and with its accent folded if that's enabled.
+    fn normalize_letter(&self, letter: char) -> char {
+        let letter = if self.case_sensitive { letter } else { letters::to_lowercase(letter) };
+        #[cfg(feature = "unicode-normalization")]
+        if self.fold_accents {
+            return letters::fold_accents(&letter.to_string()).chars().next().unwrap_or(letter);
+        }
+        letter
+    }
+
+    /// Makes `only_using_letters` and `anagrams_of` consider only the
+    /// alphabetic characters of each word from now on, so that "mother-in-law"
+    /// can be spelled with just the letters "mothernilaw". The words
+    /// themselves are unchanged, and the other filters still see every
+    /// character.
+    pub fn ignore_non_alpha(&mut self) {
+        self.ignore_non_alpha = true;
+    }
+
+    /// Removes the apostrophes from every word, and from every query from now
+    /// on, so "don't" is stored as "dont" and can be spelled with just the
+    /// letters "dont", as in Scrabble. `contains` matches both "don't" and
+    /// "dont" afterwards. Words left the same by this, like "its" and "it's",
+    /// are merged. For a lexicon created with `new_case_preserving`, the words
+    /// `iter` yields lose their apostrophes too. By default, apostrophes are
+    /// kept and count as letters.
+    pub fn strip_apostrophes(&mut self) {
+        self.strip_apostrophes = true;
+        self.map_words(letters::strip_apostrophes);
+    }
+
+    /// Returns the characters of the word that the letter-based filters look
+    /// at, which is all of them unless `ignore_non_alpha` was called.
+    fn filtered_letters(ignore_non_alpha: bool, word: &str) -> impl Iterator<Item = char> + '_ {
+        word.chars().filter(move |l| !ignore_non_alpha || l.is_alphabetic())
+    }
+
+    /// Adds a word to the end of the lexicon, normalizing it like the words
+    /// passed to the constructor. Nothing happens if the word is already in
+    /// the lexicon. This is O(n), so prefer `extend_words` for adding many
+    /// words at once.
+    pub fn push(&mut self, word: &str) {
+        let normalized = self.normalize(word);
+        if !self.words.contains(&normalized) {
+            self.append(normalized, word);
+        }
+    }
+
+    /// Adds each of the given words to the end of the lexicon, as with `push`,
+    /// but in O(n + m) time overall.
+    pub fn extend_words<I: IntoIterator<Item = String>>(&mut self, words: I) {
+        let mut seen: HashSet<String> = self.words.iter().cloned().collect();
+        for word in words {
+            let normalized = self.normalize(&word);
+            if seen.insert(normalized.clone()) {
+                self.append(normalized, &word);
+            }
+        }
+    }
+
+    /// Replaces every word with the result of `f`, such as to encode the words
+    /// with a cipher. `f` sees the words as `iter` yields them, and what it
+    /// returns is normalized like the words passed to the constructor, so a
+    /// case-insensitive lexicon stays in lowercase. Words that map to the same
+    /// result are merged, keeping the position of the first.
+    pub fn map_words<F: Fn(&str) -> String>(&mut self, f: F) {
+        let mapped: Vec<String> = self.iter().map(f).collect();
+        let empty = VecLexicon{
+            fold_accents: self.fold_accents,
+            case_sensitive: self.case_sensitive,
+            ignore_non_alpha: self.ignore_non_alpha,
+            strip_apostrophes: self.strip_apostrophes,
+            originals: self.originals.as_ref().map(|_| vec![]),
+            ..VecLexicon::default()
+        };
+        *self = empty.with_new_words(mapped);
+    }
+
+    /// Writes the words to the given file, one per line, replacing whatever
+    /// was there before. The words are written as `iter` yields them.
+    pub fn save_to_path<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        for word in self.iter() {
+            writeln!(file, "{}", word)?;
+        }
+        file.flush()
+    }
+
+    /// Reads a lexicon from a file with one word per line, as written by
+    /// `save_to_path`. Empty lines are skipped. Fails if the file cannot be
+    /// found or read, or isn't valid UTF-8.
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> io::Result<VecLexicon> {
+        let lines = BufReader::new(File::open(path)?).lines();
+        let mut words = vec![];
+        for line in lines {
+            let line = line?;
+            if !line.is_empty() {
+                words.push(line);
+            }
+        }
+        Ok(VecLexicon::new(words))
+    }
+
+    /// Returns `true` if every one of the given words is in the lexicon, as
+    /// with `contains`. This indexes the lexicon once for the whole batch, so
+    /// it's much faster than calling `contains` on each word.
+    pub fn contains_all<'a, I: IntoIterator<Item = &'a str>>(&self, words: I) -> bool {
+        let index = self.index();
+        words.into_iter().all(|word| index.contains(self.normalize(word).as_str()))
+    }
+
+    /// Returns the given words that are in the lexicon, as with `contains`, in
+    /// their original order and form. Like `contains_all`, this indexes the
+    /// lexicon once for the whole batch.
+    pub fn filter_valid<'a>(&self, words: &'a [&'a str]) -> Vec<&'a str> {
+        let index = self.index();
+        words.iter().copied().filter(|word| index.contains(self.normalize(word).as_str())).collect()
+    }
+
+    /// Returns a lexicon with every word in either lexicon. Words from `self`
+    /// come first, in their original order, followed by the new words from
+    /// `other`. The words of `other` are normalized with the settings of
+    /// `self`, which the result shares.
+    pub fn union(&self, other: &VecLexicon) -> VecLexicon {
+        let mut union = self.clone();
+        union.extend_words(other.iter().map(String::from));
+        union
+    }
+
+    /// Returns a lexicon with the words of `self` that are also in `other`,
+    /// in their original order.
+    pub fn intersection(&self, other: &VecLexicon) -> VecLexicon {
+        let other: HashSet<String> = other.words.iter().map(|word| self.normalize(word)).collect();
+        let mut intersection = self.clone();
+        intersection.retain(|word| other.contains(word));
+        intersection
+    }
+
+    /// Returns a lexicon with the words of `self` that aren't in `other`, in
+    /// their original order.
+    pub fn difference(&self, other: &VecLexicon) -> VecLexicon {
+        let mut difference = self.clone();
+        difference.remove_all_in(other);
+        difference
+    }
+
+    /// Removes every word that's also in `other`, like `difference` but in
+    /// place, such as to drop the words already played in earlier rounds.
+    /// Unlike `exclude_words`, the words of `other` are looked up in a set
+    /// built once, so this stays O(n + m) for large lexicons.
+    pub fn remove_all_in(&mut self, other: &VecLexicon) {
+        let other: HashSet<String> = other.words.iter().map(|word| self.normalize(word)).collect();
+        self.retain(|word| !other.contains(word));
+    }
+
+    /// Builds a set of the words for fast repeated lookups.
+    fn index(&self) -> HashSet<&str> {
+        self.words.iter().map(String::as_str).collect()
+    }
+
+    /// Converts the lexicon into a set of its words, as yielded by `iter`,
+    /// for fast lookups with the standard library alone. The words are moved,
+    /// not copied.
+    pub fn into_hashset(self) -> HashSet<String> {
+        self.into_iter().collect()
+    }
+
+    /// Iterates over the words currently in the lexicon without consuming it,
+    /// so it can be inspected between filters. For a lexicon created with
+    /// `new_case_preserving`, this yields the words in their original case.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.displayed().iter().map(String::as_str)
+    }
+
+    /// Returns the words in order as a slice, in the same form as `iter`, for
+    /// scanning them repeatedly without cloning. The slice borrows the
+    /// lexicon, so it has to be dropped before filtering again, and it doesn't
+    /// reflect any filters applied after that.
+    pub fn as_slice(&self) -> &[String] {
+        self.displayed()
+    }
+
+    /// Returns an uppercase copy of each word, in order, for display in the
+    /// style of Scrabble and crossword puzzles. The lexicon keeps its own
+    /// words as they are.
+    pub fn uppercased(&self) -> Vec<String> {
+        self.iter().map(str::to_uppercase).collect()
+    }
+
+    /// Returns the words as they should be shown, which are the originals if
+    /// they were kept.
+    fn displayed(&self) -> &[String] {
+        self.originals.as_deref().unwrap_or(&self.words)
+    }
+
+    /// Iterates over each normalized word along with the word as it's shown.
+    fn entries(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.words.iter().zip(self.displayed())
+    }
+
+    /// Returns the words for which `keep` returns `true`, in order. `keep`
+    /// sees the normalized words, but they're returned as `iter` yields them.
+    fn shown_where<F: Fn(&str) -> bool>(&self, keep: F) -> Vec<String> {
+        self.entries().filter(|(word, _)| keep(word)).map(|(_, shown)| shown.clone()).collect()
+    }
+
+    /// Returns a copy of the words in sorted order, leaving the lexicon
+    /// untouched. Words are compared by Unicode code point rather than by any
+    /// locale's rules, so accented letters sort after "z". The words are given
+    /// as `iter` yields them, in the order `sort` would leave them.
+    pub fn sorted(&self) -> Vec<String> {
+        let mut order: Vec<usize> = (0..self.words.len()).collect();
+        order.sort_unstable_by(|&a, &b| self.words[a].cmp(&self.words[b]));
+        let displayed = self.displayed();
+        order.into_iter().map(|i| displayed[i].clone()).collect()
+    }
+
+    /// Sorts the words in place by Unicode code point, as with `sorted`.
+    pub fn sort(&mut self) {
+        self.sort_words_by(|a, b| a.cmp(b));
+    }
+
+    /// Sorts the words in place by their length in `char`s, longest first if
+    /// `descending` is `true`, such as for a leaderboard. Words of the same
+    /// length are sorted alphabetically either way.
+    pub fn sort_by_length(&mut self, descending: bool) {
+        self.sort_words_by(|a, b| {
+            let by_length = a.chars().count().cmp(&b.chars().count());
+            let by_length = if descending { by_length.reverse() } else { by_length };
+            by_length.then_with(|| a.cmp(b))
+        });
+    }
+
+    /// Starts a lazy query, which collects filters and then applies them all
+    /// at once to each word as the results are iterated. This avoids a pass
+    /// over the whole list per filter, and leaves the lexicon untouched.
+    pub fn lazy(&self) -> LazyQuery<'_> {
+        LazyQuery{lexicon: self, filters: vec![]}
+    }
+
+    /// Groups the words by their length in `char`s, from shortest to longest,
+    /// such as for listing Spelling Bee answers by their points. Each group
+    /// keeps the order of the lexicon.
+    pub fn by_length(&self) -> BTreeMap<usize, Vec<String>> {
+        let mut groups: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+        for (word, shown) in self.entries() {
+            groups.entry(word.chars().count()).or_default().push(shown.clone());
+        }
+        groups
+    }
+
+    /// Returns every word tied for the most `char`s, in the order of the
+    /// lexicon, such as the best answers left after filtering to a rack. An
+    /// empty lexicon gives an empty list.
+    pub fn longest(&self) -> Vec<String> {
+        let max = self.words.iter().map(|word| word.chars().count()).max();
+        self.shown_where(|word| Some(word.chars().count()) == max)
+    }
+
+    /// Returns every word tied for the fewest `char`s, in the order of the
+    /// lexicon. An empty lexicon gives an empty list.
+    pub fn shortest(&self) -> Vec<String> {
+        let min = self.words.iter().map(|word| word.chars().count()).min();
+        self.shown_where(|word| Some(word.chars().count()) == min)
+    }
+
+    /// Returns `true` if any word in the lexicon starts with the given prefix,
+    /// which is normalized like a query to `contains`. For repeated lookups,
+    /// `TrieLexicon::has_prefix` is much faster.
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        let prefix = self.normalize(prefix);
+        self.words.iter().any(|word| word.starts_with(&prefix))
+    }
+
+    /// Returns up to `limit` words starting with the given prefix, as for
+    /// autocompletion, in the order they're stored. Unlike `starts_with`,
+    /// this leaves the lexicon untouched.
+    pub fn completions(&self, prefix: &str, limit: usize) -> Vec<String> {
+        let prefix = self.normalize(prefix);
+        self.entries()
+            .filter(|(word, _)| word.starts_with(&prefix))
+            .take(limit)
+            .map(|(_, shown)| shown.clone())
+            .collect()
+    }
+
+    /// Returns the words that use every distinct letter in `letters` at least
+    /// once, like the pangrams in a Spelling Bee puzzle. Unlike `with_letters`,
+    /// this leaves the lexicon untouched, so it can be called after
+    /// `only_using_letters` and `with_letter` to pick out the pangrams from
+    /// the rest of the solutions.
+    pub fn pangrams(&self, letters: &str) -> Vec<String> {
+        let letters = self.normalize(letters);
+        self.shown_where(|word| letters.chars().all(|l| word.contains(l)))
+    }
+
+    /// Returns `true` if any words are left, meaning a puzzle whose rules
+    /// have been applied as filters has at least one answer.
+    pub fn is_solvable(&self) -> bool {
+        !self.words.is_empty()
+    }
+
+    /// Returns the letters of `required` that none of the words contain, in
+    /// the order given and without repeats. When designing a Spelling Bee
+    /// puzzle, call this after `spelling_bee` with the puzzle's letters to
+    /// find any that no answer uses. Unless the lexicon is case-sensitive, the
+    /// letters are compared ignoring case and returned in lowercase.
+    pub fn missing_to_solve(&self, required: &[char]) -> Vec<char> {
+        let present: HashSet<char> = self.words.iter().flat_map(|word| word.chars()).collect();
+        let mut seen = HashSet::new();
+        required
+            .iter()
+            .map(|&letter| self.normalize_letter(letter))
+            .filter(|letter| !present.contains(letter) && seen.insert(*letter))
+            .collect()
+    }
+
+    /// Returns the words that fit the given crossword template, in which `?`
+    /// stands for an unknown letter and any other character must match
+    /// exactly, like "?r?s?". Only words with exactly as many letters as the
+    /// template match. Unlike `with_pattern`, this leaves the lexicon
+    /// untouched.
+    pub fn crossword_matches(&self, template: &str) -> Vec<String> {
+        let template: Vec<char> = self.normalize(template).chars().collect();
+        self.shown_where(|word| letters::matches_pattern(word, &template, '?'))
+    }
+
+    /// Returns the words that differ from the given word in exactly one
+    /// position, like the steps of a word ladder: "cat" has neighbors "cot",
+    /// "bat", and "car", but not "cart". The word itself isn't included.
+    pub fn neighbors(&self, word: &str) -> Vec<String> {
+        let word = self.normalize(word);
+        self.shown_where(|other| letters::one_substitution_apart(&word, other))
+    }
+
+    /// Returns the words that appear as a contiguous run of letters somewhere
+    /// within the given word, as in "Within Words" puzzles: "pant" contains
+    /// "an", "ant", and "pan". The word itself isn't included. See
+    /// `subsequences_in` to allow gaps between the letters.
+    pub fn substrings_in(&self, word: &str) -> Vec<String> {
+        let word = self.normalize(word);
+        self.shown_where(|other| other != word && word.contains(other))
+    }
+
+    /// Returns the words whose letters all appear within the given word in
+    /// the same order, but not necessarily next to each other: "pant"
+    /// contains "pat" as well as everything found by `substrings_in`. The word
+    /// itself isn't included.
+    pub fn subsequences_in(&self, word: &str) -> Vec<String> {
+        let word = self.normalize(word);
+        self.shown_where(|other| other != word && letters::is_subsequence(other, &word))
+    }
+
+    /// Returns the words that share at least the last `min_suffix` letters
+    /// with the given word, as a crude rhyme based on spelling alone: "cat"
+    /// rhymes with "bat" and "hat" for a `min_suffix` of 2, but not "car". The
+    /// word itself isn't included, and nothing is returned if it's shorter
+    /// than `min_suffix`. Every word ends with the empty string, so a
+    /// `min_suffix` of 0 is treated as 1.
+    pub fn rhymes_with(&self, word: &str, min_suffix: usize) -> Vec<String> {
+        let min_suffix = min_suffix.max(1);
+        let word = self.normalize(word);
+        let length = word.chars().count();
+        if length < min_suffix {
+            return vec![];
+        }
+        let suffix: String = word.chars().skip(length - min_suffix).collect();
+        self.shown_where(|other| other != word && other.ends_with(&suffix))
+    }
+
+    /// Returns the words that are one edit away from the given word, where an
+    /// edit inserts, deletes, or replaces a single letter: "cat" reaches
+    /// "cart", "at", and "cot". In other words, these are the words at a
+    /// Levenshtein distance of exactly 1. The word itself isn't included.
+    pub fn edit_neighbors(&self, word: &str) -> Vec<String> {
+        let word = self.normalize(word);
+        self.shown_where(|other| letters::one_edit_apart(&word, other))
+    }
+
+    /// Returns the words within `max_distance` edits of the given word, as
+    /// counted by Levenshtein distance, along with their distances. This is
+    /// useful for suggesting corrections to a misspelled word. The closest
+    /// words come first, and words at the same distance keep their order in
+    /// the lexicon. If the word itself is in the lexicon, it's included with a
+    /// distance of 0.
+    pub fn closest(&self, word: &str, max_distance: usize) -> Vec<(String, usize)> {
+        let word = self.normalize(word);
+        let length = word.chars().count();
+        let mut close: Vec<(String, usize)> = self.entries()
+            // Each edit changes the length by at most one, so this skips most
+            // of the words without computing the distance.
+            .filter(|(other, _)| other.chars().count().abs_diff(length) <= max_distance)
+            .map(|(other, shown)| (shown.clone(), letters::edit_distance(&word, other)))
+            .filter(|&(_, distance)| distance <= max_distance)
+            .collect();
+        close.sort_by_key(|&(_, distance)| distance);
+        close
+    }
+
+    /// Finds a shortest word ladder from `from` to `to`, changing one letter
+    /// at a time so that every step is a word in the lexicon. The ladder
+    /// includes both ends. Returns `None` if there's no such ladder,
+    /// including when either word isn't in the lexicon. The steps are given as
+    /// `iter` yields them.
+    pub fn build_ladder(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        let (from, to) = (self.normalize(from), self.normalize(to));
+        let length = from.chars().count();
+        // Every step keeps the same length, so only those words can be used.
+        // The search runs on the normalized words, which `from` and `to` match.
+        let shown: HashMap<&str, &str> = self.entries()
+            .filter(|(word, _)| word.chars().count() == length)
+            .map(|(word, displayed)| (word.as_str(), displayed.as_str()))
+            .collect();
+        let candidates: Vec<&str> = self.words
+            .iter()
+            .map(String::as_str)
+            .filter(|word| shown.contains_key(word))
+            .collect();
+        if !shown.contains_key(from.as_str()) || !shown.contains_key(to.as_str()) {
+            return None;
+        }
+
+        // Breadth-first search, remembering the word each one was reached from.
+        let mut previous: HashMap<&str, &str> = HashMap::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(from.as_str());
+        while let Some(word) = queue.pop_front() {
+            if word == to {
+                let mut ladder = vec![shown[word].to_string()];
+                let mut step = word;
+                while let Some(&prev) = previous.get(step) {
+                    ladder.push(shown[prev].to_string());
+                    step = prev;
+                }
+                ladder.reverse();
+                return Some(ladder);
+            }
+            for &next in &candidates {
+                let unseen = next != from && !previous.contains_key(next);
+                if unseen && letters::one_substitution_apart(word, next) {
+                    previous.insert(next, word);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the `n` words with the highest Scrabble scores, as given by
+    /// `scoring::scrabble_score`, along with their scores. Ties are broken
+    /// alphabetically. Fewer than `n` words are returned if the lexicon is
+    /// smaller than that.
+    pub fn top_scoring(&self, n: usize) -> Vec<(String, u32)> {
+        let mut scored: Vec<(&String, &String, u32)> = self.entries()
+            .map(|(word, shown)| (word, shown, scoring::scrabble_score(word)))
+            .collect();
+        scored.sort_by(|(w1, _, s1), (w2, _, s2)| s2.cmp(s1).then_with(|| w1.cmp(w2)));
+        scored.into_iter().take(n).map(|(_, shown, score)| (shown.clone(), score)).collect()
+    }
+
+    /// Returns the total Spelling Bee score of the words, as given by
+    /// `scoring::spelling_bee_score` for the puzzle with the given letters.
+    /// This is meant to be called after filtering to the puzzle's answers,
+    /// as with `spelling_bee`, to find the points available.
+    pub fn total_spelling_bee_score(&self, letters: &str) -> u32 {
+        self.words.iter().map(|word| scoring::spelling_bee_score(word, letters)).sum()
+    }
+
+    /// Returns `true` if some word of exactly the given length, counted in
+    /// `char`s, can be played from the tiles in `rack`, using each tile at
+    /// most once as in `anagrams_of`. Each `?` in the rack is a blank that can
+    /// stand for any letter, so "retinas" and "retina?" can both make a word
+    /// of length 7. The lexicon is left untouched.
+    pub fn can_form_length(&self, rack: &str, length: usize) -> bool {
+        let rack = self.normalize(rack);
+        let blanks = rack.matches('?').count();
+        let available = letters::letter_counts(&rack.replace('?', ""));
+        self.words.iter().any(|word| {
+            word.chars().count() == length && letters::fits_within_with_blanks(word, &available, blanks)
+        })
+    }
+
+    /// Returns the number of words containing each letter at least once, so
+    /// "apple" counts once for 'p'. Letters that aren't in any word are left
+    /// out. See `letter_counts` for the total number of times each letter
+    /// appears.
+    pub fn letter_histogram(&self) -> HashMap<char, usize> {
+        let mut histogram = HashMap::new();
+        for word in &self.words {
+            for letter in word.chars().collect::<HashSet<char>>() {
+                *histogram.entry(letter).or_insert(0) += 1;
+            }
+        }
+        histogram
+    }
+
+    /// Returns the total number of times each letter appears across all of
+    /// the words, so "apple" counts twice for 'p', as when tallying the tiles
+    /// needed to spell every word. Letters that aren't in any word are left
+    /// out.
+    pub fn letter_counts(&self) -> HashMap<char, usize> {
+        let mut counts = HashMap::new();
+        for word in &self.words {
+            for letter in word.chars() {
+                *counts.entry(letter).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Scores each word by how common its letters are in the lexicon, to
+    /// find informative guesses in games like Wordle. A letter's frequency is
+    /// the fraction of words that contain it, and a word's score is the sum
+    /// of the frequencies of its distinct letters, so repeated letters only
+    /// count once. The words are returned with their scores, highest first,
+    /// with ties broken alphabetically.
+    pub fn rank_by_letter_frequency(&self) -> Vec<(String, f64)> {
+        let counts = self.letter_histogram();
+        let total = self.words.len() as f64;
+        let mut ranked: Vec<(&String, &String, f64)> = self.entries()
+            .map(|(word, shown)| {
+                let letters: HashSet<char> = word.chars().collect();
+                let score = letters.iter().map(|l| counts[l] as f64 / total).sum();
+                (word, shown, score)
+            })
+            .collect();
+        ranked.sort_by(|(w1, _, s1), (w2, _, s2)| s2.total_cmp(s1).then_with(|| w1.cmp(w2)));
+        ranked.into_iter().map(|(_, shown, score)| (shown.clone(), score)).collect()
+    }
+
+    /// Returns every word in the lexicon that can be spelled on the given
+    /// Boggle board. This builds a `TrieLexicon` to search with, so convert to
+    /// one directly when solving many boards; see `TrieLexicon::solve_boggle`
+    /// for the rules.
+    ///
+    /// The tiles are read ignoring case. For a case-sensitive lexicon, every
+    /// word spelled that way is returned, so both "Polish" and "polish" can be
+    /// found, and words are returned as `iter` yields them.
+    pub fn solve_boggle<const N: usize>(&self, board: &[[char; N]; N]) -> Vec<String> {
+        // The trie holds the lowercase words, so map what it finds back to
+        // the words of this lexicon that they came from.
+        let mut shown: HashMap<String, Vec<&String>> = HashMap::new();
+        for (word, displayed) in self.entries() {
+            shown.entry(word.to_lowercase()).or_default().push(displayed);
+        }
+        TrieLexicon::new(self.words.clone())
+            .solve_boggle(board)
+            .iter()
+            .flat_map(|word| shown[word].iter().map(|&displayed| displayed.clone()))
+            .collect()
+    }
+
+    /// Picks `n` different words at random, in random order, such as for a
+    /// daily puzzle. Returns every word, shuffled, if the lexicon has fewer
+    /// than `n`. The random number generator is passed in, so seeding it
+    /// gives the same words each time. Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn sample<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Vec<String> {
+        self.displayed().choose_multiple(rng, n).cloned().collect()
+    }
+
+    /// Picks a random word with exactly `distinct_letters` different letters,
+    /// to serve as the pangram a Spelling Bee-style puzzle is built around.
+    /// Returns `None` if no word has that many. Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn random_pangram_base<R: Rng + ?Sized>(&self, distinct_letters: usize,
+                                                rng: &mut R) -> Option<String> {
+        let candidates: Vec<&String> = self.entries()
+            .filter(|(word, _)| word.chars().collect::<HashSet<char>>().len() == distinct_letters)
+            .map(|(_, shown)| shown)
+            .collect();
+        candidates.choose(rng).map(|word| word.to_string())
+    }
+
+    /// Keeps only the words that match the given regular expression in full.
+    /// The pattern is anchored at both ends internally, so there's no need to
+    /// write `^` or `$`, and it ignores case like `contains` unless the
+    /// lexicon is case-sensitive. The pattern is matched against the
+    /// normalized words, so with those options enabled, accents are already
+    /// folded and apostrophes already stripped from them. Fails if the pattern
+    /// isn't a valid regular expression, in which case the lexicon is left
+    /// unchanged. Requires the `regex` feature.
+    #[cfg(feature = "regex")]
+    pub fn matching_regex(&mut self, pattern: &str) -> Result<(), regex::Error> {
+        let re = regex::RegexBuilder::new(&format!("^(?:{})$", pattern))
+            .case_insensitive(!self.case_sensitive)
+            .build()?;
+        self.retain(|word| re.is_match(word));
+        Ok(())
+    }
+}
+
+/// The serialized form of a `VecLexicon`, which is normalized again on
+/// deserialization in case it was edited or written by hand.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerializedVecLexicon {
+    words: Vec<String>,
+    #[serde(default)]
+    fold_accents: bool,
+    #[serde(default)]
+    case_sensitive: bool,
+    #[serde(default)]
+    ignore_non_alpha: bool,
+    #[serde(default)]
+    strip_apostrophes: bool,
+    #[serde(default)]
+    originals: Option<Vec<String>>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SerializedVecLexicon> for VecLexicon {
+    fn from(lexicon: SerializedVecLexicon) -> Self {
+        let SerializedVecLexicon{
+            words,
+            fold_accents,
+            case_sensitive,
+            ignore_non_alpha,
+            strip_apostrophes,
+            originals,
+        } = lexicon;
+        // The originals normalize to the words, so they're enough to rebuild
+        // a case-preserving lexicon.
+        let (words, originals) = match originals {
+            Some(originals) => (originals, Some(vec![])),
+            None => (words, None),
+        };
+        VecLexicon{
+            fold_accents,
+            case_sensitive,
+            ignore_non_alpha,
+            strip_apostrophes,
+            originals,
+            ..VecLexicon::default()
+        }.with_new_words(words)
     }
 }
 
@@ -27,48 +864,1350 @@ impl From<Vec<String>> for VecLexicon {
     }
 }
 
+impl From<HashSet<String>> for VecLexicon {
+    /// Creates a lexicon from the words in the set, in lowercase as with
+    /// `VecLexicon::new`. The order of the words is that of the set's
+    /// iteration, which is arbitrary.
+    fn from(words: HashSet<String>) -> Self {
+        VecLexicon::default().with_new_words(words)
+    }
+}
+
+impl FromIterator<String> for VecLexicon {
+    /// Collects the words into a lexicon, in lowercase and without
+    /// duplicates as with `VecLexicon::new`.
+    fn from_iter<I: IntoIterator<Item = String>>(words: I) -> Self {
+        VecLexicon::default().with_new_words(words)
+    }
+}
+
+impl<'a> FromIterator<&'a str> for VecLexicon {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(words: I) -> Self {
+        words.into_iter().map(String::from).collect()
+    }
+}
+
+impl Extend<String> for VecLexicon {
+    /// Adds the words to the end of the lexicon, as with `extend_words`.
+    fn extend<I: IntoIterator<Item = String>>(&mut self, words: I) {
+        self.extend_words(words);
+    }
+}
+
+impl<'a> Extend<&'a str> for VecLexicon {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, words: I) {
+        self.extend_words(words.into_iter().map(String::from));
+    }
+}
+
+/// A filter that a lazy query checks against each word.
+type LazyFilter<'a> = Box<dyn Fn(&str) -> bool + 'a>;
+
+/// A chain of filters over a `VecLexicon` that are only run when the query is
+/// iterated, as created by `VecLexicon::lazy`. The filters behave like the
+/// `Lexicon` methods of the same names, and the matching words are yielded in
+/// the lexicon's order.
+pub struct LazyQuery<'a> {
+    /// The lexicon being queried.
+    lexicon: &'a VecLexicon,
+    /// The filters every word must pass, in the order they were added.
+    filters: Vec<LazyFilter<'a>>,
+}
+
+impl<'a> LazyQuery<'a> {
+    /// Adds an arbitrary filter, keeping only the words for which `keep`
+    /// returns `true`.
+    pub fn filter<F: Fn(&str) -> bool + 'a>(mut self, keep: F) -> Self {
+        self.filters.push(Box::new(keep));
+        self
+    }
+
+    /// Keeps only the words with the letter, as in `Lexicon::with_letter`.
+    /// Like every lazy filter, this normalizes its argument the way the
+    /// lexicon normalizes its words, so case is ignored unless the lexicon is
+    /// case-sensitive.
+    pub fn with_letter(self, letter: char) -> Self {
+        let letter = self.lexicon.normalize_letter(letter);
+        self.filter(move |word| word.contains(letter))
+    }
+
+    /// Skips the words with the letter, as in `Lexicon::without_letter`.
+    pub fn without_letter(self, letter: char) -> Self {
+        let letter = self.lexicon.normalize_letter(letter);
+        self.filter(move |word| !word.contains(letter))
+    }
+
+    /// Keeps only the words spelled solely from the letters, as in `Lexicon::only_using_letters`.
+    pub fn only_using_letters<T: IntoIterator<Item = char>>(self, letters: T) -> Self {
+        let string: String = letters.into_iter().map(|l| self.lexicon.normalize_letter(l)).collect();
+        let ignore = self.lexicon.ignore_non_alpha;
+        self.filter(move |word| VecLexicon::filtered_letters(ignore, word).all(|l| string.contains(l)))
+    }
+
+    /// Keeps only the words with all of the letters, counting repeats, as in `Lexicon::with_letters`.
+    pub fn with_letters<T: IntoIterator<Item = char>>(self, letters: T) -> Self {
+        let letters: String = letters.into_iter().map(|l| self.lexicon.normalize_letter(l)).collect();
+        let required = letters::letter_counts(&letters);
+        self.filter(move |word| letters::covers(word, &required))
+    }
+
+    /// Keeps only the words beginning with the prefix, as in `Lexicon::starts_with`.
+    pub fn starts_with(self, prefix: &str) -> Self {
+        let prefix = self.lexicon.normalize(prefix);
+        self.filter(move |word| word.starts_with(&prefix))
+    }
+
+    /// Keeps only the words ending with the suffix, as in `Lexicon::ends_with`.
+    pub fn ends_with(self, suffix: &str) -> Self {
+        let suffix = self.lexicon.normalize(suffix);
+        self.filter(move |word| word.ends_with(&suffix))
+    }
+
+    /// Keeps only the words containing the substring, as in `Lexicon::containing`.
+    pub fn containing(self, substring: &str) -> Self {
+        let substring = self.lexicon.normalize(substring);
+        self.filter(move |word| word.contains(&substring))
+    }
+
+    /// Keeps only the words of exactly the length, as in `Lexicon::with_exact_length`.
+    pub fn with_exact_length(self, length: usize) -> Self {
+        self.filter(move |word| word.chars().count() == length)
+    }
+
+    /// Keeps only the words longer than the length, as in `Lexicon::with_more_length`.
+    pub fn with_more_length(self, length: usize) -> Self {
+        self.filter(move |word| word.chars().count() > length)
+    }
+
+    /// Keeps only the words shorter than the length, as in `Lexicon::with_less_length`.
+    pub fn with_less_length(self, length: usize) -> Self {
+        self.filter(move |word| word.chars().count() < length)
+    }
+
+    /// Keeps only the words with lengths in the range, as in `Lexicon::with_length_in_range`.
+    pub fn with_length_in_range<R: RangeBounds<usize> + 'a>(self, range: R) -> Self {
+        self.filter(move |word| range.contains(&word.chars().count()))
+    }
+}
+
+impl<'a> IntoIterator for LazyQuery<'a> {
+    type Item = &'a str;
+    type IntoIter = LazyIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let words = self.lexicon.words.iter().zip(self.lexicon.displayed());
+        LazyIter{words, filters: self.filters}
+    }
+}
+
+/// The words matching a `LazyQuery`, found as they're iterated.
+pub struct LazyIter<'a> {
+    /// The words that haven't been checked yet, normalized and as they're
+    /// shown.
+    words: std::iter::Zip<std::slice::Iter<'a, String>, std::slice::Iter<'a, String>>,
+    /// The filters every word must pass.
+    filters: Vec<LazyFilter<'a>>,
+}
+
+impl<'a> Iterator for LazyIter<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let filters = &self.filters;
+        self.words
+            .by_ref()
+            .find(|(word, _)| filters.iter().all(|keep| keep(word)))
+            .map(|(_, shown)| shown.as_str())
+    }
+}
+
 impl IntoIterator for VecLexicon {
     type Item = String;
     type IntoIter = std::vec::IntoIter<Self::Item>;
     
+    /// Iterates over the words in order, in their original case for a
+    /// lexicon created with `new_case_preserving`.
+    fn into_iter(self) -> Self::IntoIter {
+        self.originals.unwrap_or(self.words).into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a VecLexicon {
+    type Item = &'a String;
+    type IntoIter = std::slice::Iter<'a, String>;
+
+    /// Iterates over the words in order without consuming the lexicon, as
+    /// with `iter`.
     fn into_iter(self) -> Self::IntoIter {
-        self.words.into_iter()
+        self.displayed().iter()
     }
 }
 
 impl Lexicon for VecLexicon {
     /// Returns `true` if the word list contains the given word and `false`
-    /// otherwise.
+    /// otherwise. The query is normalized first, like the stored words.
     fn contains(&self, word: &str) -> bool {
-        self.words.contains(&String::from(word))
+        self.words.contains(&self.normalize(word))
+    }
+
+    fn len(&self) -> usize {
+        self.words.len()
     }
 
     /// Keeps only the words in the list with the given letter.
     fn with_letter(&mut self, letter: char) {
-        self.words.retain(|word| word.contains(letter));
+        let letter = self.normalize_letter(letter);
+        self.retain(|word| word.contains(letter));
     }
 
     /// Keeps only the words in the list without the given letter.
     fn without_letter(&mut self, letter: char) {
-        self.words.retain(|word| !word.contains(letter));
+        let letter = self.normalize_letter(letter);
+        self.retain(|word| !word.contains(letter));
     }
 
     /// Keeps only the words that only contain the given letters. Words that
     /// don't use all of the given letters are kept, unlike `with_letters.`
     fn only_using_letters<T: IntoIterator<Item = char>>(&mut self, letters: T) {
-        let string: String = letters.into_iter().collect();
-        self.words.retain(|word| word.chars().all(|l| string.contains(l)));
+        let string: String = letters.into_iter().map(|l| self.normalize_letter(l)).collect();
+        let allowed = letters::ascii_mask(&string) & !letters::NOT_ASCII_LOWERCASE;
+        let ignore = self.ignore_non_alpha;
+        self.retain_masked(|word, mask| {
+            if mask & letters::NOT_ASCII_LOWERCASE == 0 {
+                mask & !allowed == 0
+            } else {
+                VecLexicon::filtered_letters(ignore, word).all(|l| string.contains(l))
+            }
+        });
+    }
+
+    /// Keeps only the words with all of the given letters, counting repeats:
+    /// `['l', 'l']` keeps "llama" and "hello" but not "lap".
+    fn with_letters<T: IntoIterator<Item = char>>(&mut self, letters: T) {
+        let letters: String = letters.into_iter().map(|l| self.normalize_letter(l)).collect();
+        let required = letters::letter_counts(&letters);
+        self.retain(|word| letters::covers(word, &required));
+    }
+
+    fn anagrams_of(&mut self, letters: &str) {
+        let available = letters::letter_counts(&self.normalize(letters));
+        let ignore = self.ignore_non_alpha;
+        self.retain(|word| {
+            let word: String = VecLexicon::filtered_letters(ignore, word).collect();
+            letters::fits_within(&word, &available)
+        });
+    }
+
+    fn with_pattern(&mut self, pattern: &str) {
+        let pattern: Vec<char> = self.normalize(pattern).chars().collect();
+        self.retain(|word| letters::matches_pattern(word, &pattern, '.'));
+    }
+
+    fn with_letter_at(&mut self, index: usize, letter: char) {
+        let letter = self.normalize_letter(letter);
+        self.retain(|word| word.chars().nth(index) == Some(letter));
+    }
+
+    fn apply_wordle_guess(&mut self, guess: &str, feedback: &[Feedback]) {
+        let guess = self.normalize(guess);
+        self.retain(|word| wordle::is_consistent(word, &guess, feedback));
+    }
+
+    fn starts_with(&mut self, prefix: &str) {
+        let prefix = self.normalize(prefix);
+        self.retain(|word| word.starts_with(&prefix));
+    }
+
+    fn ends_with(&mut self, suffix: &str) {
+        let suffix = self.normalize(suffix);
+        self.retain(|word| word.ends_with(&suffix));
+    }
+
+    fn containing(&mut self, substring: &str) {
+        let substring = self.normalize(substring);
+        self.retain(|word| word.contains(&substring));
+    }
+
+    fn exclude_words<'a, I: IntoIterator<Item = &'a str>>(&mut self, words: I) {
+        let excluded: HashSet<String> = words.into_iter().map(|word| self.normalize(word)).collect();
+        self.retain(|word| !excluded.contains(word));
+    }
+
+    fn retain_words<F: Fn(&str) -> bool>(&mut self, predicate: F) {
+        self.retain_serial(predicate);
     }
 
     fn with_exact_length(&mut self, length: usize) {
-        self.words.retain(|word| word.len() == length);
+        self.retain(|word| word.chars().count() == length);
     }
 
     fn with_more_length(&mut self, length: usize) {
-        self.words.retain(|word| word.len() > length);
+        self.retain(|word| word.chars().count() > length);
     }
 
     fn with_less_length(&mut self, length: usize) {
-        self.words.retain(|word| word.len() < length);
+        self.retain(|word| word.chars().count() < length);
+    }
+
+    fn with_length_in_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        // The range itself might not be safe to share between threads.
+        let bounds: (Bound<usize>, Bound<usize>) = (range.start_bound().cloned(), range.end_bound().cloned());
+        self.retain(|word| bounds.contains(&word.chars().count()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexicon::VowelPolicy;
+
+    use super::*;
+
+    fn lexicon(words: &[&str]) -> VecLexicon {
+        VecLexicon::new(words.iter().map(|&w| String::from(w)).collect())
+    }
+
+    #[test]
+    fn test_length_counts_chars() {
+        let mut lex = lexicon(&["café", "naïve", "él", "test"]);
+        lex.with_exact_length(4);
+        assert!(lex.contains("café"));
+        assert!(lex.contains("test"));
+        assert!(!lex.contains("naïve"));
+        assert!(!lex.contains("él"));
+
+        let mut lex = lexicon(&["café", "naïve", "él", "test"]);
+        lex.with_exact_length(5);
+        assert!(lex.contains("naïve"));
+        assert!(!lex.contains("café"));
+
+        let mut lex = lexicon(&["café", "naïve", "él", "test"]);
+        lex.with_more_length(4);
+        assert!(lex.contains("naïve"));
+        assert!(!lex.contains("café"));
+
+        let mut lex = lexicon(&["café", "naïve", "él", "test"]);
+        lex.with_less_length(4);
+        assert!(lex.contains("él"));
+        assert!(!lex.contains("café"));
+    }
+
+    #[test]
+    fn test_contains_ignores_case() {
+        let lex = lexicon(&["apple"]);
+        assert!(lex.contains("APPLE"));
+        assert!(lex.contains("Apple"));
+        assert!(lex.contains("apple"));
+
+        let lex = lexicon(&["Apple"]);
+        assert!(lex.contains("apple"));
+        assert!(!lex.contains("pear"));
+    }
+
+    #[test]
+    fn test_anagrams_of() {
+        let mut lex = lexicon(&["ate", "eat", "tea", "at", "eta", "tee", "bat", "teat"]);
+        lex.anagrams_of("aet");
+        assert_eq!(lex, lexicon(&["ate", "eat", "tea", "at", "eta"]));
+    }
+
+    #[test]
+    fn test_with_pattern() {
+        let mut lex = lexicon(&["crane", "crate", "cranes", "crab", "brace", "cream"]);
+        lex.with_pattern("Cra.e");
+        assert_eq!(lex, lexicon(&["crane", "crate"]));
+
+        let mut lex = lexicon(&["café", "cafe", "cafés"]);
+        lex.with_pattern("caf.");
+        assert_eq!(lex, lexicon(&["café", "cafe"]));
+    }
+
+    #[test]
+    fn test_apply_wordle_guess() {
+        use crate::wordle::Feedback::*;
+
+        let mut lex = lexicon(&["eerie", "elite", "where", "emcee", "speed", "crane"]);
+        lex.apply_wordle_guess("SPEED", &[Absent, Absent, Present, Present, Absent]);
+        assert_eq!(lex, lexicon(&["eerie", "elite"]));
+
+        // Only two of the three 'e's in the guess are in the answer.
+        let mut lex = lexicon(&["speed", "sheep", "egged", "eerie"]);
+        lex.apply_wordle_guess("eerie", &[Present, Present, Absent, Absent, Absent]);
+        assert_eq!(lex, lexicon(&["speed", "sheep"]));
+
+        let mut lex = lexicon(&["speed"]);
+        lex.apply_wordle_guess("speed", &[Correct; 4]);
+        assert!(!lex.contains("speed"));
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_matching_regex() {
+        let mut lex = lexicon(&["bter", "better", "bitter", "butter", "bettter", "betters", "abetter"]);
+        lex.matching_regex("B.t{1,2}er").unwrap();
+        assert_eq!(lex, lexicon(&["better", "bitter", "butter"]));
+
+        // Alternation shouldn't escape the anchors.
+        let mut lex = lexicon(&["cat", "cats", "dog", "hotdog"]);
+        lex.matching_regex("cat|dog").unwrap();
+        assert_eq!(lex, lexicon(&["cat", "dog"]));
+
+        let mut lex = lexicon(&["cat"]);
+        assert!(lex.matching_regex("ca(t").is_err());
+        assert!(lex.contains("cat"));
+
+        let mut lex = VecLexicon::new_case_sensitive(vec![String::from("Apple"), String::from("apple")]);
+        lex.matching_regex("apple").unwrap();
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["apple"]);
+    }
+
+    #[test]
+    fn test_starts_and_ends_with() {
+        let mut lex = lexicon(&["pre", "prefix", "press", "repress", "apple"]);
+        lex.starts_with("");
+        assert_eq!(lex, lexicon(&["pre", "prefix", "press", "repress", "apple"]));
+        lex.starts_with("PRE");
+        assert_eq!(lex, lexicon(&["pre", "prefix", "press"]));
+
+        let mut lex = lexicon(&["sing", "ring", "ing", "in", "bringing", "rang"]);
+        lex.ends_with("ing");
+        assert_eq!(lex, lexicon(&["sing", "ring", "ing", "bringing"]));
+        lex.ends_with("ringing");
+        assert_eq!(lex, lexicon(&["bringing"]));
+    }
+
+    #[test]
+    fn test_containing() {
+        let mut lex = lexicon(&["quart", "aqua", "squint", "tea", "uq"]);
+        lex.containing("");
+        assert_eq!(lex.len(), 5);
+        lex.containing("QU");
+        assert_eq!(lex, lexicon(&["quart", "aqua", "squint"]));
+    }
+
+    #[test]
+    fn test_len() {
+        let mut lex = lexicon(&["apple", "pear", "plum"]);
+        assert_eq!(lex.len(), 3);
+        lex.with_letter('e');
+        assert_eq!(lex.len(), 2);
+        assert!(!lex.is_empty());
+        lex.with_letter('z');
+        assert_eq!(lex.len(), 0);
+        assert!(lex.is_empty());
+    }
+
+    #[test]
+    fn test_iter_does_not_consume() {
+        let mut lex = lexicon(&["apple", "pear", "plum"]);
+        let first: Vec<&str> = lex.iter().collect();
+        let second: Vec<&str> = lex.iter().collect();
+        assert_eq!(first, vec!["apple", "pear", "plum"]);
+        assert_eq!(first, second);
+
+        lex.with_letter('p');
+        lex.with_letter('l');
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["apple", "plum"]);
+    }
+
+    #[test]
+    fn test_with_length_in_range() {
+        let words = ["a", "at", "ate", "late", "plate", "plates", "épée"];
+
+        let mut lex = lexicon(&words);
+        lex.with_length_in_range(2..=4);
+        assert_eq!(lex, lexicon(&["at", "ate", "late", "épée"]));
+
+        let mut lex = lexicon(&words);
+        lex.with_length_in_range(2..4);
+        assert_eq!(lex, lexicon(&["at", "ate"]));
+
+        let mut lex = lexicon(&words);
+        lex.with_length_in_range(..3);
+        assert_eq!(lex, lexicon(&["a", "at"]));
+
+        let mut lex = lexicon(&words);
+        lex.with_length_in_range(5..);
+        assert_eq!(lex, lexicon(&["plate", "plates"]));
+
+        let mut lex = lexicon(&words);
+        lex.with_length_in_range(..);
+        assert_eq!(lex.len(), words.len());
+    }
+
+    #[test]
+    fn test_top_scoring() {
+        let lex = lexicon(&["area", "quiz", "jab", "tea", "eat"]);
+        assert_eq!(lex.top_scoring(2), vec![(String::from("quiz"), 22), (String::from("jab"), 12)]);
+        let all = lex.top_scoring(10);
+        assert_eq!(all.len(), 5);
+        assert_eq!(&all[3..], &[(String::from("eat"), 3), (String::from("tea"), 3)]);
+    }
+
+    #[test]
+    fn test_pangrams() {
+        let lex = lexicon(&["doughboy", "dough", "bough", "ghoulbody", "hobby"]);
+        assert_eq!(lex.pangrams("DOUGHBY"), vec!["doughboy", "ghoulbody"]);
+        assert_eq!(lex.pangrams(""), lex.iter().collect::<Vec<&str>>());
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-normalization")]
+    fn test_new_normalized() {
+        let mut lex = VecLexicon::new_normalized(vec![String::from("naïve"), String::from("Café")]);
+        assert!(lex.contains("naive"));
+        assert!(lex.contains("naïve"));
+        assert!(lex.contains("CAFE"));
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["naive", "cafe"]);
+        lex.with_letter('ï');
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["naive"]);
+
+        let lex = lexicon(&["naïve"]);
+        assert!(!lex.contains("naive"));
+    }
+
+    #[test]
+    fn test_incremental_building() {
+        let mut lex = VecLexicon::default();
+        assert!(lex.is_empty());
+        lex.push("Apple");
+        lex.push("pear");
+        assert_eq!(lex.len(), 2);
+        assert!(lex.contains("apple"));
+
+        lex.extend_words(vec![String::from("PLUM"), String::from("fig")]);
+        assert_eq!(lex, lexicon(&["apple", "pear", "plum", "fig"]));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_round_trip() {
+        let lex = lexicon(&["apple", "pear", "café"]);
+        let json = serde_json::to_string(&lex).unwrap();
+        let back: VecLexicon = serde_json::from_str(&json).unwrap();
+        assert_eq!(lex, back);
+
+        let edited: VecLexicon = serde_json::from_str(r#"{"words": ["Apple", "PEAR"]}"#).unwrap();
+        assert_eq!(edited, lexicon(&["apple", "pear"]));
+
+        let preserving = VecLexicon::new_case_preserving(vec![String::from("Apple")]);
+        let back: VecLexicon = serde_json::from_str(&serde_json::to_string(&preserving).unwrap()).unwrap();
+        assert_eq!(preserving, back);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("lexi-save-{}.txt", std::process::id()));
+        let lex = lexicon(&["apple", "Pear", "café"]);
+        lex.save_to_path(&path).unwrap();
+        assert_eq!(VecLexicon::load_from_path(&path).unwrap(), lex);
+
+        std::fs::write(&path, "apple\n\nPEAR\n\n").unwrap();
+        let loaded = VecLexicon::load_from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), lexicon(&["apple", "pear"]));
+    }
+
+    #[test]
+    fn test_only_using_letters_limited() {
+        let mut lex = lexicon(&["be", "bee", "b", "eb", "bed"]);
+        lex.only_using_letters_limited(vec!['b', 'e']);
+        assert_eq!(lex, lexicon(&["be", "b", "eb"]));
+
+        let mut lex = lexicon(&["be", "bee", "b", "eb", "bed"]);
+        lex.only_using_letters(vec!['b', 'e']);
+        assert_eq!(lex, lexicon(&["be", "bee", "b", "eb"]));
+    }
+
+    #[test]
+    fn test_batch_queries() {
+        let lex = lexicon(&["the", "cat", "sat", "on", "mat"]);
+        assert!(lex.contains_all(vec!["The", "cat", "sat"]));
+        assert!(!lex.contains_all(vec!["the", "cat", "sgat"]));
+        assert!(lex.contains_all(vec![]));
+        assert_eq!(lex.filter_valid(&["The", "cat", "sgat", "on", "teh", "mat"]),
+                   vec!["The", "cat", "on", "mat"]);
+    }
+
+    #[test]
+    fn test_with_letter_at() {
+        let mut lex = lexicon(&["apple", "cat", "spa", "a", "épée"]);
+        lex.with_letter_at(1, 'p');
+        assert_eq!(lex, lexicon(&["apple", "spa", "épée"]));
+        lex.with_letter_at(2, 'é');
+        assert_eq!(lex, lexicon(&["épée"]));
+        lex.with_letter_at(10, 'é');
+        assert!(lex.is_empty());
+    }
+
+    #[test]
+    fn test_exclude_words() {
+        let mut lex = lexicon(&["apple", "cat", "dog", "pear"]);
+        lex.exclude_words(vec!["Apple", "cat", "zebra"]);
+        assert_eq!(lex, lexicon(&["dog", "pear"]));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let fruit = lexicon(&["apple", "pear", "plum", "apple"]);
+        let red = lexicon(&["Apple", "cherry", "brick"]);
+        let animals = lexicon(&["cat", "dog"]);
+
+        assert_eq!(fruit.union(&red), lexicon(&["apple", "pear", "plum", "cherry", "brick"]));
+        assert_eq!(fruit.intersection(&red), lexicon(&["apple"]));
+        assert_eq!(fruit.difference(&red), lexicon(&["pear", "plum"]));
+
+        let deduped = lexicon(&["apple", "pear", "plum"]);
+        assert_eq!(fruit.intersection(&fruit), deduped);
+        assert_eq!(deduped.intersection(&deduped).intersection(&deduped), deduped);
+        assert_eq!(deduped.difference(&animals), deduped);
+        assert!(fruit.difference(&fruit).is_empty());
+    }
+
+    #[test]
+    fn test_missing_to_solve() {
+        let mut lex = lexicon(&["good", "hood", "dough", "cat"]);
+        lex.spelling_bee("dughbz", 'o', 4);
+        assert!(lex.is_solvable());
+        assert_eq!(lex.missing_to_solve(&['d', 'o', 'u', 'g', 'h', 'b', 'z', 'b']), vec!['b', 'z']);
+        assert!(lex.missing_to_solve(&['o', 'd']).is_empty());
+
+        lex.with_letter('z');
+        assert!(!lex.is_solvable());
+        assert_eq!(lex.missing_to_solve(&['o']), vec!['o']);
+
+        let lex = lexicon(&["good", "dog"]);
+        assert!(lex.missing_to_solve(&['G', 'O']).is_empty());
+        assert_eq!(lex.missing_to_solve(&['Z', 'z']), vec!['z']);
+    }
+
+    #[test]
+    fn test_without_substrings() {
+        let mut lex = lexicon(&["quit", "cat", "oxygen", "equal", "xylophone"]);
+        lex.without_substrings(vec!["QU", "xy"]);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["cat"]);
+
+        let mut lex = lexicon(&["quit", "cat"]);
+        lex.without_substrings(vec![]);
+        assert_eq!(lex.len(), 2);
+    }
+
+    #[test]
+    fn test_using_exactly_letters() {
+        let words = &["god", "dog", "dogs", "do", "good", "cat"];
+        let mut lex = lexicon(words);
+        lex.using_exactly_letters("dog".chars());
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["god", "dog", "good"]);
+
+        let mut trie = TrieLexicon::new(words.iter().map(|&w| String::from(w)).collect());
+        trie.using_exactly_letters("ddoogg".chars());
+        assert_eq!(trie.into_iter().collect::<Vec<String>>(), vec!["dog", "god", "good"]);
+    }
+
+    #[test]
+    fn test_as_slice() {
+        let mut lex = lexicon(&["apple", "Pear", "fig"]);
+        assert_eq!(lex.as_slice(), ["apple", "pear", "fig"]);
+        assert!(lex.as_slice().iter().map(String::as_str).eq(lex.iter()));
+        assert!(lex.as_slice().iter().all(|word| lex.contains(word)));
+
+        lex.with_letter('p');
+        assert_eq!(lex.as_slice().len(), lex.len());
+        assert_eq!(lex.as_slice(), ["apple", "pear"]);
+    }
+
+    #[test]
+    fn test_borrowed_iteration() {
+        let lex = lexicon(&["apple", "Pear"]);
+        let mut seen = vec![];
+        for word in &lex {
+            seen.push(word.clone());
+        }
+        assert_eq!(seen, vec!["apple", "pear"]);
+        assert_eq!(lex.len(), 2);
+        assert!(lex.contains("pear"));
+
+        let lex = VecLexicon::new_case_preserving(vec![String::from("Pear")]);
+        assert_eq!((&lex).into_iter().collect::<Vec<&String>>(), vec!["Pear"]);
+    }
+
+    #[test]
+    fn test_restrict_to_alphabet() {
+        let mut lex = lexicon(&["quiz", "zebra", "don't", "café", "apple"]);
+        lex.restrict_to_alphabet("abcdefghijklmnoprstuvwxyz");
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["zebra", "don't", "apple"]);
+
+        let mut lex = VecLexicon::new_case_sensitive(vec![String::from("Apple"), String::from("Éclair")]);
+        lex.restrict_to_alphabet("ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["Apple"]);
+    }
+
+    #[test]
+    fn test_longest_and_shortest() {
+        let mut lex = lexicon(&["at", "cat", "apple", "zebra!", "to"]);
+        lex.with_letter('a');
+        lex.without_letter('z');
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["at", "cat", "apple"]);
+        assert_eq!(lex.longest(), vec!["apple"]);
+        assert_eq!(lex.shortest(), vec!["at"]);
+
+        let lex = lexicon(&["at", "cat", "to", "dog"]);
+        assert_eq!(lex.longest(), vec!["cat", "dog"]);
+        assert_eq!(lex.shortest(), vec!["at", "to"]);
+        assert!(VecLexicon::default().longest().is_empty());
+    }
+
+    #[test]
+    fn test_map_words() {
+        fn rot13(word: &str) -> String {
+            word.chars()
+                .map(|c| match c {
+                    'a'..='z' => (((c as u8 - b'a' + 13) % 26) + b'a') as char,
+                    'A'..='Z' => (((c as u8 - b'A' + 13) % 26) + b'A') as char,
+                    _ => c,
+                })
+                .collect()
+        }
+
+        let original = lexicon(&["apple", "zebra", "don't"]);
+        let mut lex = original.clone();
+        lex.map_words(rot13);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["nccyr", "mroen", "qba'g"]);
+        assert!(lex.contains("nccyr"));
+        lex.map_words(rot13);
+        assert_eq!(lex, original);
+
+        let mut lex = lexicon(&["apple", "pear"]);
+        lex.map_words(|word| word.to_uppercase());
+        assert_eq!(lex, lexicon(&["apple", "pear"]));
+        lex.map_words(|_| String::from("same"));
+        assert_eq!(lex, lexicon(&["same"]));
+    }
+
+    #[test]
+    fn test_contains_token() {
+        let lex = lexicon(&["apple", "don't", "mother-in-law"]);
+        assert!(lex.contains_token("apple,"));
+        assert!(lex.contains_token("\"Apple!\""));
+        assert!(lex.contains_token("don't."));
+        assert!(lex.contains_token("(mother-in-law)"));
+        assert!(!lex.contains_token("dont"));
+        assert!(!lex.contains_token("..."));
+        assert!(!lex.contains("apple,"));
+    }
+
+    #[test]
+    fn test_remove_all_in() {
+        let mut lex = lexicon(&["apple", "pear", "plum", "fig", "kiwi"]);
+        let played = lexicon(&["PEAR", "kiwi", "grape"]);
+        lex.remove_all_in(&played);
+        assert_eq!(lex, lexicon(&["apple", "plum", "fig"]));
+
+        let mut all = lexicon(&["apple", "plum", "fig"]);
+        all.remove_all_in(&lex);
+        assert!(all.is_empty());
+    }
+
+    #[test]
+    fn test_new_removes_duplicates() {
+        assert_eq!(VecLexicon::new(vec!["Apple".into(), "apple".into()]).len(), 1);
+
+        let lex = lexicon(&["pear", "Apple", "pear", "apple", "fig"]);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["pear", "apple", "fig"]);
+
+        let mut lex = lexicon(&["pear"]);
+        lex.push("PEAR");
+        lex.extend_words(vec![String::from("fig"), String::from("Pear"), String::from("fig")]);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["pear", "fig"]);
+    }
+
+    #[test]
+    fn test_case_sensitive() {
+        let words = vec![String::from("Polish"), String::from("polish"), String::from("NASA")];
+        let lex = VecLexicon::new_case_sensitive(words.clone());
+        assert_eq!(lex.len(), 3);
+        assert!(lex.contains("Polish"));
+        assert!(lex.contains("polish"));
+        assert!(!lex.contains("POLISH"));
+        assert!(!lex.contains("nasa"));
+
+        let mut proper = lex.clone();
+        proper.starts_with("P");
+        assert_eq!(proper.iter().collect::<Vec<&str>>(), vec!["Polish"]);
+
+        // The default constructor still folds case.
+        assert_eq!(VecLexicon::new(words).len(), 2);
+    }
+
+    #[test]
+    fn test_collect() {
+        let lex: VecLexicon = ["A", "b"].iter().map(|&w| String::from(w)).collect();
+        assert!(lex.contains("a"));
+        assert!(lex.contains("b"));
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["a", "b"]);
+
+        let lex: VecLexicon = "the cat saw The dog".split(' ').collect();
+        assert_eq!(lex, lexicon(&["the", "cat", "saw", "dog"]));
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut lex = lexicon(&["apple"]);
+        lex.extend(vec![String::from("Pear"), String::from("APPLE")]);
+        lex.extend("FIG pear".split(' '));
+        assert_eq!(lex.len(), 3);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["apple", "pear", "fig"]);
+        assert!(lex.iter().all(|word| word == word.to_lowercase()));
+    }
+
+    #[test]
+    fn test_shrink() {
+        let words: Vec<String> = (0..1000).map(|i| format!("word{}", i)).collect();
+        let mut lex = VecLexicon::new(words);
+        let capacity = lex.words.capacity();
+        assert!(capacity >= 1000);
+
+        lex.ends_with("99");
+        assert_eq!(lex.len(), 10);
+        assert!(lex.words.capacity() < capacity);
+        assert!(lex.masks.capacity() < capacity);
+
+        // Removing a few words keeps the room for them until asked.
+        lex.without_letter('1');
+        assert_eq!(lex.len(), 9);
+        assert!(lex.words.capacity() >= 10);
+        lex.shrink();
+        assert_eq!(lex.words.capacity(), 9);
+    }
+
+    #[test]
+    fn test_case_preserving() {
+        let lex = VecLexicon::new_case_preserving(vec![String::from("Apple")]);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["Apple"]);
+        assert!(lex.contains("apple"));
+        assert!(lex.contains("APPLE"));
+
+        let words = ["NASA", "Zebra", "apple", "nasa", "Mango"].iter().map(|&w| String::from(w)).collect();
+        let mut lex = VecLexicon::new_case_preserving(words);
+        lex.with_letter('a');
+        lex.sort();
+        assert_eq!(lex.lazy().with_less_length(5).into_iter().collect::<Vec<&str>>(), vec!["NASA"]);
+        assert_eq!(lex.into_iter().collect::<Vec<String>>(), vec!["apple", "Mango", "NASA", "Zebra"]);
+    }
+
+    #[test]
+    fn test_rank_by_letter_frequency() {
+        let lex = lexicon(&["arose", "eerie", "vivid", "stare"]);
+        let ranked = lex.rank_by_letter_frequency();
+        let order: Vec<&str> = ranked.iter().map(|(w, _)| w.as_str()).collect();
+        assert_eq!(order, vec!["arose", "stare", "eerie", "vivid"]);
+        // "arose" and "stare" tie, with a, r, o, s, e in 2, 3, 1, 2, 3 words.
+        assert!((ranked[0].1 - 11.0 / 4.0).abs() < 1e-9);
+        // The repeated letters of "vivid" only count once.
+        assert!((ranked[3].1 - 4.0 / 4.0).abs() < 1e-9);
+        assert!(VecLexicon::default().rank_by_letter_frequency().is_empty());
+    }
+
+    #[test]
+    fn test_sorted() {
+        let mut lex = lexicon(&["pear", "Apple", "éclair", "zebra", "apricot"]);
+        let sorted = lex.sorted();
+        assert!(sorted.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(sorted, vec!["apple", "apricot", "pear", "zebra", "éclair"]);
+        assert_eq!(lex.iter().next(), Some("pear"));
+
+        lex.sort();
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), sorted);
+
+        let mut lex = VecLexicon::new_case_preserving(vec![String::from("Zebra"), String::from("apple")]);
+        let sorted = lex.sorted();
+        assert_eq!(sorted, vec!["apple", "Zebra"]);
+        lex.sort();
+        assert_eq!(lex.as_slice(), sorted.as_slice());
+    }
+
+    #[test]
+    fn test_ignore_non_alpha() {
+        let mut lex = lexicon(&["mother-in-law", "o'clock", "moth"]);
+        lex.only_using_letters("motherinlaw".chars());
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["moth"]);
+
+        let mut lex = lexicon(&["mother-in-law", "o'clock", "moth"]);
+        lex.ignore_non_alpha();
+        lex.only_using_letters("motherinlaw".chars());
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["mother-in-law", "moth"]);
+
+        let mut lex = lexicon(&["mother-in-law", "o'clock", "moth"]);
+        lex.ignore_non_alpha();
+        lex.anagrams_of("lockco");
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["o'clock"]);
+    }
+
+    #[test]
+    fn test_strip_apostrophes() {
+        let mut kept = lexicon(&["don't", "dont", "do", "it's", "its"]);
+        assert_eq!(kept.len(), 5);
+        kept.only_using_letters("dont".chars());
+        assert_eq!(kept.iter().collect::<Vec<&str>>(), vec!["dont", "do"]);
+
+        let mut stripped = lexicon(&["don't", "do", "it's", "its"]);
+        stripped.strip_apostrophes();
+        assert_eq!(stripped.iter().collect::<Vec<&str>>(), vec!["dont", "do", "its"]);
+        assert!(stripped.contains("don't"));
+        assert!(stripped.contains("dont"));
+        stripped.only_using_letters("dont".chars());
+        assert_eq!(stripped.iter().collect::<Vec<&str>>(), vec!["dont", "do"]);
+
+        stripped.push("Can\u{2019}t");
+        assert!(stripped.contains("cant"));
+
+        let mut preserved = VecLexicon::new_case_preserving(vec![String::from("Don't"), String::from("do")]);
+        preserved.strip_apostrophes();
+        assert_eq!(preserved.iter().collect::<Vec<&str>>(), vec!["Dont", "do"]);
+        assert!(preserved.contains("don't"));
+        preserved.push("It's");
+        assert_eq!(preserved.as_slice().last().map(String::as_str), Some("Its"));
+    }
+
+    #[test]
+    fn test_letter_histogram() {
+        let histogram = lexicon(&["apple", "ant"]).letter_histogram();
+        assert_eq!(histogram[&'a'], 2);
+        assert_eq!(histogram[&'p'], 1);
+        assert_eq!(histogram[&'l'], 1);
+        assert_eq!(histogram[&'e'], 1);
+        assert_eq!(histogram[&'n'], 1);
+        assert_eq!(histogram[&'t'], 1);
+        assert_eq!(histogram.len(), 6);
+    }
+
+    #[test]
+    fn test_letter_counts() {
+        let counts = lexicon(&["apple"]).letter_counts();
+        let expected: HashMap<char, usize> = [('a', 1), ('p', 2), ('l', 1), ('e', 1)].iter().copied().collect();
+        assert_eq!(counts, expected);
+
+        let lex = lexicon(&["apple", "pop"]);
+        assert_eq!(lex.letter_counts()[&'p'], 4);
+        assert_eq!(lex.letter_histogram()[&'p'], 2);
+    }
+
+    #[test]
+    fn test_crossword_matches() {
+        let lex = lexicon(&["apple", "ample", "apples", "maple", "arise", "prose"]);
+        assert_eq!(lex.crossword_matches("?pp?e"), vec!["apple"]);
+        assert_eq!(lex.crossword_matches("?r?s?"), vec!["arise", "prose"]);
+        assert_eq!(lex.crossword_matches("A???E"), vec!["apple", "ample", "arise"]);
+        assert!(lex.crossword_matches("????").is_empty());
+        assert_eq!(lex.len(), 6);
+    }
+
+    #[test]
+    fn test_with_letters_counts_duplicates() {
+        let mut lex = lexicon(&["llama", "hello", "lap", "pal"]);
+        lex.with_letters(vec!['l', 'l']);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["llama", "hello"]);
+
+        let mut lex = lexicon(&["llama", "hello", "lap", "pal"]);
+        lex.with_letters(vec!['l', 'a']);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["llama", "lap", "pal"]);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let lex = lexicon(&["cat", "cot", "bat", "car", "cart", "dog", "at"]);
+        assert_eq!(lex.neighbors("cat"), vec!["cot", "bat", "car"]);
+        assert_eq!(lex.neighbors("CAT"), vec!["cot", "bat", "car"]);
+        assert!(lex.neighbors("dog").is_empty());
+    }
+
+    #[test]
+    fn test_build_ladder() {
+        let lex = lexicon(&["cold", "cord", "card", "ward", "warm", "word", "worm", "wore"]);
+        let ladder = lex.build_ladder("cold", "warm").unwrap();
+        assert_eq!(ladder.len(), 5);
+        assert_eq!(ladder.first().map(String::as_str), Some("cold"));
+        assert_eq!(ladder.last().map(String::as_str), Some("warm"));
+        assert!(ladder.windows(2).all(|pair| letters::one_substitution_apart(&pair[0], &pair[1])));
+
+        assert_eq!(lex.build_ladder("cold", "cold"), Some(vec![String::from("cold")]));
+        assert_eq!(lex.build_ladder("cold", "wore"), Some(vec![
+            String::from("cold"), String::from("cord"), String::from("word"), String::from("wore"),
+        ]));
+        assert_eq!(lex.build_ladder("cold", "heat"), None);
+        let lex = lexicon(&["cat", "dog"]);
+        assert_eq!(lex.build_ladder("cat", "dog"), None);
+
+        let lex = VecLexicon::new_case_preserving(vec![String::from("Cat"), String::from("Cot")]);
+        assert_eq!(lex.build_ladder("cat", "cot"), Some(vec![String::from("Cat"), String::from("Cot")]));
+    }
+
+    #[test]
+    fn test_edit_neighbors() {
+        let lex = lexicon(&["cat", "cart", "at", "cot", "scat", "cast", "dog", "act", "carts"]);
+        assert_eq!(lex.edit_neighbors("cat"), vec!["cart", "at", "cot", "scat", "cast"]);
+        assert_eq!(lex.edit_neighbors("cart"), vec!["cat", "cast", "carts"]);
+        assert!(lex.edit_neighbors("dog").is_empty());
+    }
+
+    #[test]
+    fn test_closest() {
+        let lex = lexicon(&["banana", "apple", "ale", "apples", "maple", "ample"]);
+        assert_eq!(lex.closest("aple", 1), vec![
+            (String::from("apple"), 1), (String::from("ale"), 1),
+            (String::from("maple"), 1), (String::from("ample"), 1),
+        ]);
+        let within_two = lex.closest("aple", 2);
+        assert_eq!(within_two.len(), 5);
+        assert_eq!(within_two.last(), Some(&(String::from("apples"), 2)));
+        assert_eq!(lex.closest("Apple", 0), vec![(String::from("apple"), 0)]);
+        assert!(lex.closest("xyz", 1).is_empty());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(letters::edit_distance("kitten", "sitting"), 3);
+        assert_eq!(letters::edit_distance("", "abc"), 3);
+        assert_eq!(letters::edit_distance("flaw", "lawn"), 2);
+        assert_eq!(letters::edit_distance("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_lazy_matches_eager() {
+        let lex = lexicon(&["cab", "abaca", "bad", "Acacia", "cabbage", "aback", "baa"]);
+        let lazy: Vec<&str> = lex.lazy()
+            .with_letter('a')
+            .only_using_letters("abc".chars())
+            .with_more_length(2)
+            .filter(|word| word.starts_with('a'))
+            .into_iter()
+            .collect();
+
+        let mut eager = lex.clone();
+        eager.with_letter('a');
+        eager.only_using_letters("abc".chars());
+        eager.with_more_length(2);
+        eager.starts_with("a");
+        assert_eq!(lazy, eager.iter().collect::<Vec<&str>>());
+        assert_eq!(lazy, vec!["abaca"]);
+
+        let lazy = lex.lazy().with_letters(vec!['b', 'b']).with_length_in_range(..=6);
+        assert_eq!(lazy.into_iter().next(), None);
+        assert_eq!(lex.lazy().into_iter().count(), lex.len());
+
+        let upper: Vec<&str> = lex.lazy()
+            .with_letter('A')
+            .without_letter('G')
+            .with_letters(vec!['B'])
+            .into_iter()
+            .collect();
+        let lower: Vec<&str> = lex.lazy()
+            .with_letter('a')
+            .without_letter('g')
+            .with_letters(vec!['b'])
+            .into_iter()
+            .collect();
+        assert_eq!(upper, lower);
+        assert_eq!(upper, vec!["cab", "abaca", "bad", "aback", "baa"]);
+    }
+
+    #[test]
+    fn test_retain_words() {
+        let mut lex = lexicon(&["level", "apple", "Alpha", "tent", "a"]);
+        lex.retain_words(|word| word.chars().next() == word.chars().last());
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["level", "alpha", "tent", "a"]);
+    }
+
+    #[test]
+    fn test_total_spelling_bee_score() {
+        let mut lex = lexicon(&["good", "hoodoo", "doughboy", "dog", "gold"]);
+        lex.spelling_bee("dughby", 'o', 4);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["good", "hoodoo", "doughboy"]);
+        assert_eq!(lex.total_spelling_bee_score("doughby"), 1 + 6 + 15);
+    }
+
+    #[test]
+    fn test_uppercased() {
+        let lex = lexicon(&["quiz", "Café"]);
+        assert_eq!(lex.uppercased(), vec!["QUIZ", "CAFÉ"]);
+        assert!(lex.contains("quiz"));
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["quiz", "café"]);
+    }
+
+    #[test]
+    fn test_can_form_length() {
+        let lex = lexicon(&["stainer", "retains", "tarns", "at", "quixotic"]);
+        assert!(lex.can_form_length("retinas", 7));
+        assert!(lex.can_form_length("RETINAS", 5));
+        assert!(lex.can_form_length("retinas", 2));
+        assert!(!lex.can_form_length("retinas", 8));
+        assert!(!lex.can_form_length("retinas", 6));
+        assert!(!lex.can_form_length("rstqqqq", 5));
+        assert!(lex.can_form_length("rst??qq", 5));
+        assert_eq!(lex.len(), 5);
+    }
+
+    #[test]
+    fn test_anagrams_of_with_blanks() {
+        let words = &["cat", "cab", "car", "cart", "act", "a", "tab", "zzz", "ca"];
+        // The rack "ca?", with the blank counted separately.
+        let mut lex = lexicon(words);
+        lex.anagrams_of_with_blanks("ca", 1);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["cat", "cab", "car", "act", "a", "ca"]);
+
+        let mut lex = lexicon(words);
+        lex.anagrams_of_with_blanks("ca", 0);
+        let mut plain = lexicon(words);
+        plain.anagrams_of("ca");
+        assert_eq!(lex, plain);
+
+        let mut lex = lexicon(words);
+        lex.anagrams_of_with_blanks("", 3);
+        assert_eq!(lex.len(), words.len() - 1);
+    }
+
+    #[test]
+    fn test_exact_anagrams() {
+        let mut lex = lexicon(&["silent", "enlist", "lent", "tinsel", "listens", "listen", "google"]);
+        lex.exact_anagrams("LISTEN");
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["silent", "enlist", "tinsel", "listen"]);
+        lex.exclude_words(vec!["listen"]);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["silent", "enlist", "tinsel"]);
+    }
+
+    #[test]
+    fn test_vowel_and_consonant_counts() {
+        let mut lex = lexicon(&["queue", "rhythm", "eerie", "mother-in-law", "cat"]);
+        lex.with_vowel_count(4);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["queue", "eerie", "mother-in-law"]);
+
+        let mut lex = lexicon(&["queue", "rhythm", "eerie", "mother-in-law", "cat"]);
+        lex.with_vowel_count(0);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["rhythm"]);
+
+        let mut lex = lexicon(&["queue", "rhythm", "eerie", "mother-in-law", "cat"]);
+        lex.with_consonant_count(7);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["mother-in-law"]);
+        let mut lex = lexicon(&["queue", "rhythm", "eerie", "mother-in-law", "cat"]);
+        lex.with_consonant_count(6);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["rhythm"]);
+    }
+
+    #[test]
+    fn test_vowel_policy() {
+        let mut lex = lexicon(&["rhythm", "yes", "cat"]);
+        lex.with_vowel_count_using(0, VowelPolicy::AeiouOnly);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["rhythm"]);
+
+        let mut lex = lexicon(&["rhythm", "yes", "cat"]);
+        lex.with_vowel_count_using(1, VowelPolicy::AeiouY);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["rhythm", "cat"]);
+
+        let mut lex = lexicon(&["rhythm", "yes", "cat"]);
+        lex.with_consonant_count_using(5, VowelPolicy::AeiouY);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["rhythm"]);
+        assert_eq!(VowelPolicy::default(), VowelPolicy::AeiouOnly);
+    }
+
+    #[test]
+    fn test_with_syllable_count() {
+        let mut lex = lexicon(&["cat", "apple", "beautiful", "make", "banana"]);
+        lex.with_syllable_count(1);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["cat", "make"]);
+    }
+
+    #[test]
+    fn test_rhymes_with() {
+        let lex = lexicon(&["cat", "bat", "hat", "car", "at", "combat", "act"]);
+        assert_eq!(lex.rhymes_with("cat", 2), vec!["bat", "hat", "at", "combat"]);
+        assert_eq!(lex.rhymes_with("cat", 3), Vec::<String>::new());
+        assert_eq!(lex.rhymes_with("wombat", 4), vec!["combat"]);
+        assert!(lex.rhymes_with("at", 3).is_empty());
+        assert_eq!(lex.rhymes_with("cat", 0), lex.rhymes_with("cat", 1));
+        assert_eq!(lex.rhymes_with("cat", 0), vec!["bat", "hat", "at", "combat", "act"]);
+        assert!(lex.rhymes_with("", 0).is_empty());
+    }
+
+    #[test]
+    fn test_solve_boggle() {
+        let board = [['c', 'a', 't'],
+                     ['x', 'e', 'r'],
+                     ['x', 'x', 'x']];
+        let lex = preserved(&["Cat", "rat", "Tea", "dog"]);
+        assert_eq!(lex.solve_boggle(&board), vec!["Cat", "rat", "Tea"]);
+
+        let lex = VecLexicon::new_case_sensitive(vec![String::from("Cat"), String::from("tea")]);
+        assert_eq!(lex.solve_boggle(&board), vec!["Cat", "tea"]);
+        assert!(!lex.contains("cat"));
+    }
+
+    fn preserved(words: &[&str]) -> VecLexicon {
+        VecLexicon::new_case_preserving(words.iter().map(|&w| String::from(w)).collect())
+    }
+
+    #[test]
+    fn test_case_preserving_lengths() {
+        let lex = preserved(&["Cat", "Dog", "Apple", "Ox"]);
+        assert_eq!(lex.by_length()[&3], vec!["Cat", "Dog"]);
+        assert_eq!(lex.longest(), vec!["Apple"]);
+        assert_eq!(lex.shortest(), vec!["Ox"]);
+    }
+
+    #[test]
+    fn test_case_preserving_matches() {
+        let lex = preserved(&["Good", "Dog", "God", "Paris"]);
+        assert_eq!(lex.completions("go", 5), vec!["Good", "God"]);
+        assert_eq!(lex.crossword_matches("?o?"), vec!["Dog", "God"]);
+        assert_eq!(lex.pangrams("dog"), vec!["Good", "Dog", "God"]);
+        assert_eq!(lex.neighbors("dig"), vec!["Dog"]);
+        assert_eq!(lex.edit_neighbors("gods"), vec!["God"]);
+        assert_eq!(lex.closest("gid", 1), vec![(String::from("God"), 1)]);
+        assert_eq!(lex.rhymes_with("log", 2), vec!["Dog"]);
+        assert_eq!(lex.substrings_in("godly"), vec!["God"]);
+        assert_eq!(lex.subsequences_in("goads"), vec!["God"]);
+    }
+
+    #[test]
+    fn test_case_preserving_rankings() {
+        let lex = preserved(&["Zebra", "apple", "Quiz"]);
+        assert_eq!(lex.top_scoring(1), vec![(String::from("Quiz"), scoring::scrabble_score("quiz"))]);
+        let ranked: Vec<String> = lex.rank_by_letter_frequency().into_iter().map(|(word, _)| word).collect();
+        assert_eq!(ranked.len(), 3);
+        assert!(ranked.contains(&String::from("Zebra")));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_case_preserving_random() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let lex = preserved(&["Cat", "Dog"]);
+        let mut sample = lex.sample(2, &mut StdRng::seed_from_u64(7));
+        sample.sort();
+        assert_eq!(sample, vec!["Cat", "Dog"]);
+        let base = lex.random_pangram_base(3, &mut StdRng::seed_from_u64(7)).unwrap();
+        assert!(base == "Cat" || base == "Dog");
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let lex = lexicon(&["apple", "banana", "cherry", "date", "elderberry", "fig"]);
+        let sample = lex.sample(3, &mut StdRng::seed_from_u64(7));
+        assert_eq!(sample.len(), 3);
+        assert_eq!(sample.iter().collect::<HashSet<_>>().len(), 3);
+        assert!(sample.iter().all(|word| lex.contains(word)));
+        assert_eq!(sample, lex.sample(3, &mut StdRng::seed_from_u64(7)));
+
+        let mut everything = lex.sample(10, &mut StdRng::seed_from_u64(7));
+        everything.sort();
+        assert_eq!(everything, lex.sorted());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_random_pangram_base() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let lex = lexicon(&["doughboy", "bough", "hydrogen", "prancing", "cat"]);
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..10 {
+            let base = lex.random_pangram_base(7, &mut rng).unwrap();
+            assert_eq!(base.chars().collect::<HashSet<char>>().len(), 7);
+            assert!(base == "prancing" || base == "doughboy");
+        }
+        assert_eq!(lex.random_pangram_base(3, &mut rng), Some(String::from("cat")));
+        assert_eq!(lex.random_pangram_base(9, &mut rng), None);
+    }
+
+    #[test]
+    fn test_sort_by_length() {
+        let mut lex = lexicon(&["bb", "café", "a", "ccc", "aa", "dddd", "abc"]);
+        lex.sort_by_length(true);
+        let words: Vec<&str> = lex.iter().collect();
+        assert!(words.windows(2).all(|pair| pair[0].chars().count() >= pair[1].chars().count()));
+        assert_eq!(words, vec!["café", "dddd", "abc", "ccc", "aa", "bb", "a"]);
+
+        lex.sort_by_length(false);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["a", "aa", "bb", "abc", "ccc", "café", "dddd"]);
+    }
+
+    #[test]
+    fn test_only_using_letters_mixed_words() {
+        // Words outside a to z fall back to checking each letter.
+        let mut lex = lexicon(&["café", "face", "a-ha", "aha", "ace"]);
+        lex.only_using_letters("acefé".chars());
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["café", "face", "ace"]);
+
+        let mut lex = lexicon(&["café", "face", "a-ha", "aha", "ace"]);
+        lex.only_using_letters("ah-".chars());
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["a-ha", "aha"]);
+
+        // Filters and sorting keep the masks in line with the words.
+        let mut lex = lexicon(&["cab", "zebra", "bad", "abba"]);
+        lex.sort();
+        lex.without_letter('c');
+        lex.push("dab");
+        lex.only_using_letters("abd".chars());
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["abba", "bad", "dab"]);
+    }
+
+    #[test]
+    fn test_hashset_round_trip() {
+        let set: HashSet<String> = ["Apple", "banana", "cherry"].iter().map(|&w| String::from(w)).collect();
+        let lex = VecLexicon::from(set);
+        assert_eq!(lex.len(), 3);
+        assert!(lex.contains("apple"));
+
+        let expected: HashSet<String> = ["apple", "banana", "cherry"].iter().map(|&w| String::from(w)).collect();
+        let round_trip = lex.into_hashset();
+        assert_eq!(round_trip, expected);
+        assert_eq!(VecLexicon::from(round_trip.clone()).into_hashset(), round_trip);
+    }
+
+    #[test]
+    fn test_completions() {
+        let lex = lexicon(&["apply", "apple", "apt", "banana", "applesauce"]);
+        assert!(lex.has_prefix("APP"));
+        assert!(!lex.has_prefix("apz"));
+        assert_eq!(lex.completions("app", 10), vec!["apply", "apple", "applesauce"]);
+        assert_eq!(lex.completions("app", 2), vec!["apply", "apple"]);
+        assert!(lex.completions("c", 10).is_empty());
+    }
+
+    #[test]
+    fn test_filters_preserve_order() {
+        let words = ["zebra", "apple", "mango", "Apple", "kiwi", "banana", "cherry"];
+        let mut lex = lexicon(&words);
+        assert_eq!(lex.iter().collect::<Vec<&str>>(),
+                   vec!["zebra", "apple", "mango", "kiwi", "banana", "cherry"]);
+        lex.without_letter('e');
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["mango", "kiwi", "banana"]);
+        lex.with_letter('a');
+        assert_eq!(lex.iter().collect::<Vec<&str>>(), vec!["mango", "banana"]);
+    }
+
+    #[test]
+    fn test_by_length() {
+        let groups = lexicon(&["at", "cat", "apple", "be", "café"]).by_length();
+        assert_eq!(groups.keys().copied().collect::<Vec<usize>>(), vec![2, 3, 4, 5]);
+        assert_eq!(groups[&2], vec!["at", "be"]);
+        assert_eq!(groups[&3], vec!["cat"]);
+        assert_eq!(groups[&4], vec!["café"]);
+        assert_eq!(groups[&5], vec!["apple"]);
+        assert!(VecLexicon::default().by_length().is_empty());
+    }
+
+    #[test]
+    fn test_words_within() {
+        let lex = lexicon(&["an", "ant", "pan", "pat", "pant", "tap", "a", "nap"]);
+        assert_eq!(lex.substrings_in("pant"), vec!["an", "ant", "pan", "a"]);
+        assert_eq!(lex.substrings_in("PANT"), vec!["an", "ant", "pan", "a"]);
+        assert_eq!(lex.subsequences_in("pant"), vec!["an", "ant", "pan", "pat", "a"]);
+        assert!(lex.substrings_in("xyz").is_empty());
     }
 }
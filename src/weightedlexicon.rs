@@ -0,0 +1,202 @@
+//! Implements `Lexicon` for words that each carry a weight, such as how often
+//! they're used, so that solvers can prefer common words. The words are kept
+//! in a `VecLexicon`, which does all of the filtering, and the weights are
+//! looked up by word.
+//!
+//! Like `VecLexicon`, this lexicon is case-insensitive, and converts everything
+//! to lowercase internally.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeBounds;
+
+use crate::lexicon::Lexicon;
+use crate::veclexicon::VecLexicon;
+use crate::wordle::Feedback;
+
+/// A list of words, each with a weight.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WeightedLexicon {
+    /// The words, in their original order.
+    lexicon: VecLexicon,
+    /// The weight of each word in `lexicon`, and of no others.
+    weights: HashMap<String, f64>,
+}
+
+impl WeightedLexicon {
+    /// Creates a new lexicon with the given words and weights, in lowercase.
+    /// If a word appears more than once, only its first weight is kept, just
+    /// as `VecLexicon` keeps the first copy of each word.
+    pub fn new(words: Vec<(String, f64)>) -> WeightedLexicon {
+        let lexicon = VecLexicon::new(words.iter().map(|(word, _)| word.clone()).collect());
+        let mut weights = HashMap::with_capacity(lexicon.len());
+        for (word, weight) in words {
+            weights.entry(lexicon.normalize(&word)).or_insert(weight);
+        }
+        WeightedLexicon{lexicon, weights}
+    }
+
+    /// Returns the weight of the given word, or `None` if it isn't in the
+    /// lexicon.
+    pub fn weight(&self, word: &str) -> Option<f64> {
+        self.weights.get(&self.lexicon.normalize(word)).copied()
+    }
+
+    /// Returns the `n` words with the highest weights, along with their
+    /// weights. Ties are broken alphabetically. Fewer than `n` words are
+    /// returned if the lexicon is smaller than that.
+    pub fn most_common(&self, n: usize) -> Vec<(String, f64)> {
+        let mut weighted: Vec<(String, f64)> = self.lexicon
+            .iter()
+            .map(|word| (word.to_string(), self.weights[word]))
+            .collect();
+        weighted.sort_by(|(w1, s1), (w2, s2)| s2.total_cmp(s1).then_with(|| w1.cmp(w2)));
+        weighted.truncate(n);
+        weighted
+    }
+
+    /// Returns the words without their weights.
+    pub fn as_lexicon(&self) -> &VecLexicon {
+        &self.lexicon
+    }
+
+    /// Applies a filter to the words, then drops the weights of the words
+    /// that were removed.
+    fn filter<F: FnOnce(&mut VecLexicon)>(&mut self, filter: F) {
+        filter(&mut self.lexicon);
+        if self.weights.len() != self.lexicon.len() {
+            let remaining: HashSet<&str> = self.lexicon.iter().collect();
+            self.weights.retain(|word, _| remaining.contains(word.as_str()));
+        }
+    }
+}
+
+impl From<WeightedLexicon> for VecLexicon {
+    fn from(lexicon: WeightedLexicon) -> Self {
+        lexicon.lexicon
+    }
+}
+
+impl IntoIterator for WeightedLexicon {
+    type Item = String;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    /// Iterates over the words, without their weights, in their original
+    /// order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.lexicon.into_iter()
+    }
+}
+
+impl Lexicon for WeightedLexicon {
+    fn contains(&self, word: &str) -> bool {
+        self.weights.contains_key(&self.lexicon.normalize(word))
+    }
+
+    fn len(&self) -> usize {
+        self.lexicon.len()
+    }
+
+    fn with_letter(&mut self, letter: char) {
+        self.filter(|lex| lex.with_letter(letter));
+    }
+
+    fn without_letter(&mut self, letter: char) {
+        self.filter(|lex| lex.without_letter(letter));
+    }
+
+    fn only_using_letters<T: IntoIterator<Item = char>>(&mut self, letters: T) {
+        self.filter(|lex| lex.only_using_letters(letters));
+    }
+
+    /// Keeps only the words with all of the given letters, counting repeats
+    /// like `VecLexicon`.
+    fn with_letters<T: IntoIterator<Item = char>>(&mut self, letters: T) {
+        self.filter(|lex| lex.with_letters(letters));
+    }
+
+    fn anagrams_of(&mut self, letters: &str) {
+        self.filter(|lex| lex.anagrams_of(letters));
+    }
+
+    fn with_pattern(&mut self, pattern: &str) {
+        self.filter(|lex| lex.with_pattern(pattern));
+    }
+
+    fn with_letter_at(&mut self, index: usize, letter: char) {
+        self.filter(|lex| lex.with_letter_at(index, letter));
+    }
+
+    fn apply_wordle_guess(&mut self, guess: &str, feedback: &[Feedback]) {
+        self.filter(|lex| lex.apply_wordle_guess(guess, feedback));
+    }
+
+    fn starts_with(&mut self, prefix: &str) {
+        self.filter(|lex| lex.starts_with(prefix));
+    }
+
+    fn ends_with(&mut self, suffix: &str) {
+        self.filter(|lex| lex.ends_with(suffix));
+    }
+
+    fn containing(&mut self, substring: &str) {
+        self.filter(|lex| lex.containing(substring));
+    }
+
+    fn exclude_words<'a, I: IntoIterator<Item = &'a str>>(&mut self, words: I) {
+        self.filter(|lex| lex.exclude_words(words));
+    }
+
+    fn retain_words<F: Fn(&str) -> bool>(&mut self, predicate: F) {
+        self.filter(|lex| lex.retain_words(predicate));
+    }
+
+    fn with_exact_length(&mut self, length: usize) {
+        self.filter(|lex| lex.with_exact_length(length));
+    }
+
+    fn with_more_length(&mut self, length: usize) {
+        self.filter(|lex| lex.with_more_length(length));
+    }
+
+    fn with_less_length(&mut self, length: usize) {
+        self.filter(|lex| lex.with_less_length(length));
+    }
+
+    fn with_length_in_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.filter(|lex| lex.with_length_in_range(range));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lexicon(words: &[(&str, f64)]) -> WeightedLexicon {
+        WeightedLexicon::new(words.iter().map(|&(w, f)| (String::from(w), f)).collect())
+    }
+
+    #[test]
+    fn test_most_common() {
+        let lex = lexicon(&[
+            ("the", 500.0), ("aardvark", 0.5), ("Cat", 20.0), ("dog", 20.0), ("THE", 1.0),
+        ]);
+        assert_eq!(lex.len(), 4);
+        assert_eq!(lex.most_common(1), vec![(String::from("the"), 500.0)]);
+        assert_eq!(lex.most_common(3), vec![
+            (String::from("the"), 500.0), (String::from("cat"), 20.0), (String::from("dog"), 20.0),
+        ]);
+        assert_eq!(lex.most_common(10).len(), 4);
+        assert_eq!(lex.weight("CAT"), Some(20.0));
+        assert_eq!(lex.weight("bird"), None);
+    }
+
+    #[test]
+    fn test_filters_drop_weights() {
+        let mut lex = lexicon(&[("the", 500.0), ("aardvark", 0.5), ("cat", 20.0)]);
+        lex.with_letter('a');
+        assert_eq!(lex.most_common(1), vec![(String::from("cat"), 20.0)]);
+        assert!(!lex.contains("the"));
+        assert_eq!(lex.weight("the"), None);
+        assert_eq!(lex, lexicon(&[("aardvark", 0.5), ("cat", 20.0)]));
+    }
+}
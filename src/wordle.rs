@@ -0,0 +1,85 @@
+//! Scoring for [Wordle](https://www.nytimes.com/games/wordle)-style guesses,
+//! used by `Lexicon::apply_wordle_guess` to narrow down the possible answers.
+//!
+//! Duplicate letters follow the game's rules: each letter in the answer can
+//! only be matched once, with exact matches taking priority and the remaining
+//! copies going to the leftmost misplaced letters in the guess. If the answer is
+//! "eerie" and the guess is "speed", for instance, both 'e's in the guess are
+//! `Present`; for the answer "speed" and the guess "eerie", the first two 'e's
+//! are `Present` but the last is `Absent`, because "speed" only has two.
+
+/// The feedback for a single letter of a guess.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Feedback {
+    /// The letter is in the answer at this position (green).
+    Correct,
+    /// The letter is in the answer, but not at this position (yellow).
+    Present,
+    /// The letter is not in the answer, or all of its copies in the answer are
+    /// already accounted for (grey).
+    Absent,
+}
+
+/// Returns the feedback the game would give for `guess` if the answer were
+/// `answer`, or `None` if the two have different lengths.
+pub fn score_guess(guess: &str, answer: &str) -> Option<Vec<Feedback>> {
+    let guess: Vec<char> = guess.chars().collect();
+    let answer: Vec<char> = answer.chars().collect();
+    if guess.len() != answer.len() {
+        return None;
+    }
+
+    let mut feedback = vec![Feedback::Absent; guess.len()];
+    // The answer letters that haven't been matched yet.
+    let mut unmatched = vec![];
+    for (i, (&g, &a)) in guess.iter().zip(&answer).enumerate() {
+        if g == a {
+            feedback[i] = Feedback::Correct;
+        } else {
+            unmatched.push(a);
+        }
+    }
+
+    for (i, g) in guess.iter().enumerate() {
+        if feedback[i] == Feedback::Correct {
+            continue;
+        }
+        if let Some(pos) = unmatched.iter().position(|a| a == g) {
+            unmatched.swap_remove(pos);
+            feedback[i] = Feedback::Present;
+        }
+    }
+
+    Some(feedback)
+}
+
+/// Returns `true` if `answer` could be the answer given that `guess` received
+/// the given feedback.
+pub(crate) fn is_consistent(answer: &str, guess: &str, feedback: &[Feedback]) -> bool {
+    score_guess(guess, answer).is_some_and(|score| score == feedback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::Feedback::*;
+
+    #[test]
+    fn test_score_guess() {
+        assert_eq!(score_guess("crane", "crane"), Some(vec![Correct; 5]));
+        assert_eq!(score_guess("crane", "react"),
+                   Some(vec![Present, Present, Correct, Absent, Present]));
+        assert_eq!(score_guess("crane", "cat"), None);
+    }
+
+    #[test]
+    fn test_score_guess_duplicates() {
+        assert_eq!(score_guess("speed", "eerie"),
+                   Some(vec![Absent, Absent, Present, Present, Absent]));
+        assert_eq!(score_guess("eerie", "speed"),
+                   Some(vec![Present, Present, Absent, Absent, Absent]));
+        // The green 'e' uses up the only 'e' in the answer.
+        assert_eq!(score_guess("geese", "those"),
+                   Some(vec![Absent, Absent, Absent, Correct, Correct]));
+    }
+}
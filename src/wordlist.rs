@@ -3,27 +3,34 @@
 //! [2of12inf](http://wordlist.aspell.net/12dicts-readme/#2of12inf) word list
 //! from Alan Beale, and the rights for the list are as described there. This
 //! list excludes capitalizations, abbrevations, etc., but includes swears.
-//! There are three flags that control what words are excluded:
+//! There are four flags that control what words are excluded:
 //!  - Plurals of uncountable nouns. These are often almost never used, but can
-//!  often be argued as correct. Consider, for instance, the noun "bread", which
-//!  is generally considered to not take an 's'. Yet the sentence "They had many
-//!  different breads at the store" still parses as correct. The line here is
-//!  very difficult to find, so it's a flag instead. (Note that "bread" is also
-//!  a verb, like breading eggplant, so "breads" is in both lists.)
+//!    often be argued as correct. Consider, for instance, the noun "bread", which
+//!    is generally considered to not take an 's'. Yet the sentence "They had many
+//!    different breads at the store" still parses as correct. The line here is
+//!    very difficult to find, so it's a flag instead. (Note that "bread" is also
+//!    a verb, like breading eggplant, so "breads" is in both lists.)
 //!  - Obscene words. Specifically, because the source list doesn't distinguish
-//!  these, I use a separate list found
-//!  [here.](https://github.com/chucknorris-io/swear-words) This is
-//!  by no means complete, and definitely has a few things I'd leave in, but
-//!  it's the best I could find.
+//!    these, I use a separate list found
+//!    [here.](https://github.com/chucknorris-io/swear-words) This is
+//!    by no means complete, and definitely has a few things I'd leave in, but
+//!    it's the best I could find.
 //!  - Neologisms. As time moves on (I think these were added in 2016), these
-//!  become more and more expected, and so it's recommended to include these for
-//!  words like "anime" and "blogger" that are pretty standard by now.
+//!    become more and more expected, and so it's recommended to include these for
+//!    words like "anime" and "blogger" that are pretty standard by now.
+//!  - Proper nouns. 2of12inf itself has none, but related lists do, and trivia
+//!    games might want them. Any entry starting with an uppercase letter is
+//!    treated as one.
 
+use std::collections::HashSet;
 use std::fs::File;
-use std::io::{BufRead, BufReader, Result};
+use std::io::{BufRead, BufReader, ErrorKind};
 use std::path::Path;
 
+use crate::error::{LexiError, Result};
+use crate::letters;
 use crate::veclexicon::VecLexicon;
+use crate::weightedlexicon::WeightedLexicon;
 
 const NEOLOGISM_ANNOT: char = '!';
 const UNCOUNTABLE_PLURAL_ANNOT: char = '%';
@@ -31,7 +38,7 @@ const UNCOUNTABLE_PLURAL_ANNOT: char = '%';
 /// The different flags controlling excluded and included words in the list. See
 /// the module-level documentation for more information.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-pub(crate) enum Flag {
+pub enum Flag {
     /// Include plurals of nouns that typically don't have them, like
     /// "acrimoniousnesses".
     UncountablePlurals,
@@ -39,10 +46,14 @@ pub(crate) enum Flag {
     Swears,
     /// Include newer words. Recommended for many words that have become very
     /// standard, like "barista".
-    Neologisms
+    Neologisms,
+    /// Include proper nouns, like "Paris". These are the entries of the main
+    /// list that start with an uppercase letter.
+    ProperNouns,
 }
 
 /// A list of words with the ability to filter using the flags defined in `Flag`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WordList {
     /// Words that are included in any list.
     normal_words: Vec<String>,
@@ -52,36 +63,207 @@ pub struct WordList {
     swears: Vec<String>,
     /// Neologisms.
     neologisms: Vec<String>,
+    /// Proper nouns, with their original capitalization.
+    proper_nouns: Vec<String>,
+    /// The swears as `censor` matches them: lowercase, with their words
+    /// separated by single spaces.
+    swear_phrases: HashSet<String>,
+    /// The most words in any swear phrase.
+    longest_swear: usize,
 }
 
 impl WordList {
-    /// Returns the list of words with the given flags applied. No guarantees
-    /// are made as to order.
-    pub(crate) fn custom_list<T: IntoIterator<Item = Flag>>(self, flags: T) -> Vec<String> {
-        let mut list = self.normal_words.clone();
-        let flags_iter: Vec<Flag> = flags.into_iter().collect();
-        if flags_iter.contains(&Flag::UncountablePlurals) {
-            list.extend(self.uncountable_plurals.into_iter());
+    /// Creates a list from its categories, indexing the swears once so that
+    /// `censor` doesn't have to for every text.
+    fn new(normal_words: Vec<String>, uncountable_plurals: Vec<String>, swears: Vec<String>,
+           neologisms: Vec<String>, proper_nouns: Vec<String>) -> WordList {
+        let swear_phrases: HashSet<String> = swears
+            .iter()
+            .map(|swear| lowercase_words(swear, &word_spans(swear)).join(" "))
+            .collect();
+        let longest_swear = swear_phrases.iter().map(|swear| swear.split(' ').count()).max().unwrap_or(0);
+        WordList{
+            normal_words,
+            uncountable_plurals,
+            swears,
+            neologisms,
+            proper_nouns,
+            swear_phrases,
+            longest_swear,
+        }
+    }
+
+    /// Returns the list of words with the given flags applied. The normal words
+    /// come first, followed by the uncountable plurals, swears, neologisms,
+    /// and proper nouns that the flags include, in that order whatever the
+    /// order of `flags`. Each group keeps the order of the source files.
+    ///
+    /// ```
+    /// use lexi::{Flag, Lexicon, VecLexicon, MAIN_WORDLIST_PATH, SWEARS_PATH};
+    /// use lexi::wordlist::parse_list;
+    ///
+    /// let words = parse_list(MAIN_WORDLIST_PATH, SWEARS_PATH).unwrap().custom_list(&[Flag::Swears]);
+    /// let lexicon = VecLexicon::new(words);
+    /// assert!(lexicon.contains("fuck"));
+    /// assert!(!lexicon.contains("blogger"));
+    /// ```
+    pub fn custom_list(self, flags: &[Flag]) -> Vec<String> {
+        let flags: HashSet<Flag> = flags.iter().copied().collect();
+        let mut list = self.normal_words;
+        if flags.contains(&Flag::UncountablePlurals) {
+            list.extend(self.uncountable_plurals);
         }
 
-        if flags_iter.contains(&Flag::Swears) {
-            list.extend(self.swears.into_iter());
+        if flags.contains(&Flag::Swears) {
+            list.extend(self.swears);
         }
 
-        if flags_iter.contains(&Flag::Neologisms) {
-            list.extend(self.neologisms.into_iter());
+        if flags.contains(&Flag::Neologisms) {
+            list.extend(self.neologisms);
+        }
+
+        if flags.contains(&Flag::ProperNouns) {
+            list.extend(self.proper_nouns);
         }
 
         list
     }
 
-    /// Returns the default list, with neologisms but without swears and
-    /// uncountable plurals.
-    pub(crate) fn default_list(self) -> Vec<String> {
-        self.custom_list(vec![Flag::Neologisms])
+    /// Returns the words that are in every list, whatever the flags.
+    pub fn normal_words(&self) -> &[String] {
+        &self.normal_words
+    }
+
+    /// Returns the plurals of uncountable nouns, included with
+    /// `Flag::UncountablePlurals`.
+    pub fn uncountable_plurals(&self) -> &[String] {
+        &self.uncountable_plurals
+    }
+
+    /// Returns the swears, included with `Flag::Swears`.
+    pub fn swears(&self) -> &[String] {
+        &self.swears
+    }
+
+    /// Returns the neologisms, included with `Flag::Neologisms`.
+    pub fn neologisms(&self) -> &[String] {
+        &self.neologisms
+    }
+
+    /// Returns the proper nouns, included with `Flag::ProperNouns`.
+    pub fn proper_nouns(&self) -> &[String] {
+        &self.proper_nouns
+    }
+
+    /// Returns `true` if the given text uses any of the swears in this list,
+    /// ignoring case and punctuation, as a quick profanity check on user
+    /// input. See `censor` for how the text is split into words.
+    pub fn contains_swear(&self, text: &str) -> bool {
+        !self.swear_spans(text).is_empty()
+    }
+
+    /// Returns the given text with each letter of every swear replaced by an
+    /// asterisk, so "what the fuck?" becomes "what the ****?". The text is
+    /// split into words at anything other than a letter or digit, and
+    /// everything else, including the punctuation and spacing around swears,
+    /// is left alone. Swears of several words, like "ball gag", are found
+    /// however the words are separated.
+    pub fn censor(&self, text: &str) -> String {
+        let mut censored = String::with_capacity(text.len());
+        let mut end = 0;
+        for (start, stop) in self.swear_spans(text) {
+            censored.push_str(&text[end..start]);
+            censored.extend(text[start..stop].chars().map(|_| '*'));
+            end = stop;
+        }
+        censored.push_str(&text[end..]);
+        censored
+    }
+
+    /// Returns the byte ranges of the words in the text that are part of a
+    /// swear, in order. Where swears overlap, the longest one starting
+    /// first wins.
+    fn swear_spans(&self, text: &str) -> Vec<(usize, usize)> {
+        let spans = word_spans(text);
+        let words = lowercase_words(text, &spans);
+        let mut found = vec![];
+        let mut i = 0;
+        while i < words.len() {
+            let matched = (1..=self.longest_swear.min(words.len() - i))
+                .rev()
+                .find(|&n| self.swear_phrases.contains(&words[i..i + n].join(" ")));
+            match matched {
+                Some(n) => {
+                    found.extend_from_slice(&spans[i..i + n]);
+                    i += n;
+                }
+                None => i += 1,
+            }
+        }
+        found
+    }
+
+    /// Combines this list with another, such as a supplement of technical
+    /// terms, so that flags can be applied to both at once. Each category
+    /// holds the words from either list, with those of `self` first and no
+    /// word repeated. As when parsing, a swear from either list is left out
+    /// of the other categories, whatever its case.
+    pub fn merge(self, other: WordList) -> WordList {
+        let swears = merge_unique(self.swears, other.swears);
+        let swear_set: HashSet<String> = swears.iter().map(|swear| swear.to_lowercase()).collect();
+        let merge_clean = |a: Vec<String>, b: Vec<String>| {
+            let mut merged = merge_unique(a, b);
+            merged.retain(|word| !swear_set.contains(&word.to_lowercase()));
+            merged
+        };
+
+        let normal_words = merge_clean(self.normal_words, other.normal_words);
+        let uncountable_plurals = merge_clean(self.uncountable_plurals, other.uncountable_plurals);
+        let neologisms = merge_clean(self.neologisms, other.neologisms);
+        let proper_nouns = merge_clean(self.proper_nouns, other.proper_nouns);
+        WordList::new(normal_words, uncountable_plurals, swears, neologisms, proper_nouns)
+    }
+
+    /// Returns the default list, with neologisms but without swears,
+    /// uncountable plurals, and proper nouns.
+    pub fn default_list(self) -> Vec<String> {
+        self.custom_list(&[Flag::Neologisms])
     }
 }
 
+/// Returns the byte ranges of the words in the text, taking a word to be a
+/// run of letters and digits.
+fn word_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+    spans
+}
+
+/// Returns the words of the text at the given byte ranges, in lowercase.
+fn lowercase_words(text: &str, spans: &[(usize, usize)]) -> Vec<String> {
+    spans.iter().map(|&(start, end)| text[start..end].to_lowercase()).collect()
+}
+
+/// Returns the words of `a` followed by those of `b`, keeping only the first
+/// copy of each.
+fn merge_unique(a: Vec<String>, b: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    a.into_iter().chain(b).filter(|word| seen.insert(word.clone())).collect()
+}
+
 impl From<WordList> for Vec<String> {
     fn from(words: WordList) -> Self {
         words.default_list()
@@ -95,39 +277,217 @@ impl From<WordList> for VecLexicon {
 
 }
 
+/// Options controlling how the main word list is parsed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ParseOptions {
+    /// The trailing character marking neologisms. Defaults to `!`.
+    pub neologism_annotation: char,
+    /// The trailing character marking plurals of uncountable nouns. Defaults
+    /// to `%`.
+    pub uncountable_plural_annotation: char,
+    /// The fewest letters a word can have to be kept, if any.
+    pub min_length: Option<usize>,
+    /// The most letters a word can have to be kept, if any.
+    pub max_length: Option<usize>,
+    /// Whether to remove apostrophes from every word, so that "don't" is read
+    /// as "dont", for games like Scrabble that don't allow them. Defaults to
+    /// `false`.
+    pub strip_apostrophes: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions{
+            neologism_annotation: NEOLOGISM_ANNOT,
+            uncountable_plural_annotation: UNCOUNTABLE_PLURAL_ANNOT,
+            min_length: None,
+            max_length: None,
+            strip_apostrophes: false,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Strips the annotation, if any, from a line of the main list, returning
+    /// the bare word and the flag it belongs under. This is how the main list
+    /// is annotated unless `parse_reader_annotated` is given another way:
+    /// a trailing annotation character gives the flag, and otherwise words
+    /// starting with an uppercase letter are proper nouns. An annotated word
+    /// keeps the flag of its annotation even if it's capitalized.
+    pub fn split_annotation(&self, line: &str) -> (String, Option<Flag>) {
+        let (word, flag) = if let Some(word) = line.strip_suffix(self.neologism_annotation) {
+            (word, Some(Flag::Neologisms))
+        } else if let Some(word) = line.strip_suffix(self.uncountable_plural_annotation) {
+            (word, Some(Flag::UncountablePlurals))
+        } else if line.starts_with(char::is_uppercase) {
+            (line, Some(Flag::ProperNouns))
+        } else {
+            (line, None)
+        };
+        (word.to_string(), flag)
+    }
+
+    /// Removes the apostrophes from the word if `strip_apostrophes` is set.
+    fn clean(&self, word: &str) -> String {
+        if self.strip_apostrophes { letters::strip_apostrophes(word) } else { word.to_string() }
+    }
+
+    /// Returns `true` if the word's length, in chars, is within the bounds
+    /// given by `min_length` and `max_length`.
+    fn length_in_bounds(&self, word: &str) -> bool {
+        let length = word.chars().count();
+        length >= self.min_length.unwrap_or(0)
+            && length <= self.max_length.unwrap_or(usize::MAX)
+    }
+}
+
 /// Generates a WordList from the two input files. The first one is the main
 /// word list, and marks neologisms with a trailing `!` and uncountable plurals
-/// with a trailing `#`. Fails if the file cannot be found or read.
+/// with a trailing `%`. Fails with `LexiError::Io` if either file cannot be
+/// found or read, or with `LexiError::Parse` if a line isn't valid UTF-8.
 pub fn parse_list<T: AsRef<Path>, U: AsRef<Path>>(main_list: T, swears_list: U) -> Result<WordList> {
-    let main_file = File::open(main_list)?;
-    let swears_file = File::open(swears_list)?;
+    parse_list_with(main_list, swears_list, &ParseOptions::default())
+}
 
-    let main_lines = BufReader::new(main_file).lines();
-    let swears_lines = BufReader::new(swears_file).lines();
+/// Generates a WordList from the two input files like `parse_list`, but with
+/// the annotations and length bounds given in `options` instead of the
+/// defaults.
+pub fn parse_list_with<T: AsRef<Path>, U: AsRef<Path>>(main_list: T, swears_list: U,
+                                                     options: &ParseOptions) -> Result<WordList> {
+    let main_file = BufReader::new(File::open(main_list)?);
+    let swears_file = BufReader::new(File::open(swears_list)?);
+    parse_reader_with(main_file, swears_file, options)
+}
 
-    // I don't think this can fail?
-    let swears: Vec<String> = swears_lines.map(|l| l.unwrap()).collect();
+/// Generates a WordList from the main list and several swear lists, such as
+/// the bundled English list and one for another locale, with the annotations
+/// and length bounds given in `options`. A word in any of the swear lists is
+/// a swear, and the swears are kept in the order of the lists, without
+/// repeats.
+pub fn parse_list_with_swears<T: AsRef<Path>, U: AsRef<Path>>(main_list: T, swears_lists: &[U],
+                                                            options: &ParseOptions) -> Result<WordList> {
+    let main_file = BufReader::new(File::open(main_list)?);
+    let swears_files = swears_lists
+        .iter()
+        .map(|path| Ok(BufReader::new(File::open(path)?)))
+        .collect::<Result<Vec<_>>>()?;
+    parse_reader_with_swears(main_file, swears_files, options)
+}
+
+/// Generates a WordList from the two input strings. The first one is the main
+/// word list, and marks neologisms with a trailing `!` and uncountable plurals
+/// with a trailing `%`. Strings are always valid UTF-8, so unlike `parse_list`
+/// this never fails in practice.
+pub fn parse_strings(main_list: &str, swears_list: &str) -> Result<WordList> {
+    parse_reader(main_list.as_bytes(), swears_list.as_bytes())
+}
+
+/// Generates a WordList from any two buffered readers, such as standard input
+/// or the body of an HTTP response, with the same format as `parse_list`.
+/// Fails if either reader fails or produces invalid UTF-8, as for
+/// `parse_list`.
+pub fn parse_reader<R: BufRead, S: BufRead>(main_list: R, swears_list: S) -> Result<WordList> {
+    parse_reader_with(main_list, swears_list, &ParseOptions::default())
+}
+
+/// Generates a WordList from two buffered readers like `parse_reader`, but
+/// with the annotations given in `options` instead of the defaults. Words
+/// outside the length bounds in `options` are skipped entirely, though swears
+/// of any length are still left out of the other lists.
+pub fn parse_reader_with<R: BufRead, S: BufRead>(main_list: R, swears_list: S,
+                                                 options: &ParseOptions) -> Result<WordList> {
+    parse_reader_annotated(main_list, swears_list, options, |line| options.split_annotation(line))
+}
+
+/// Generates a WordList from two buffered readers, using `annotate` to read
+/// each line of the main list instead of looking for trailing annotations.
+/// It returns the word on the line and the flag it belongs under, or `None`
+/// for a normal word, so lists marked some other way, like with a leading
+/// "N:" for neologisms, can still be parsed. The annotation characters of
+/// `options` are ignored, but its length bounds and `strip_apostrophes` still
+/// apply. `Flag::Swears` is treated like `None`, since swears come from their
+/// own list.
+///
+/// ```
+/// use lexi::Flag;
+/// use lexi::wordlist::{parse_reader_annotated, ParseOptions};
+///
+/// let list = parse_reader_annotated("apple\nN:blogger\n".as_bytes(), "".as_bytes(),
+///                                   &ParseOptions::default(), |line| {
+///     match line.strip_prefix("N:") {
+///         Some(word) => (word.to_string(), Some(Flag::Neologisms)),
+///         None => (line.to_string(), None),
+///     }
+/// }).unwrap();
+/// assert_eq!(list.neologisms(), ["blogger"]);
+/// ```
+pub fn parse_reader_annotated<R, S, F>(main_list: R, swears_list: S, options: &ParseOptions,
+                                       annotate: F) -> Result<WordList>
+where
+    R: BufRead,
+    S: BufRead,
+    F: Fn(&str) -> (String, Option<Flag>),
+{
+    parse_swears_annotated(main_list, vec![swears_list], options, annotate)
+}
+
+/// Generates a WordList from a main list and several swear lists, all given
+/// as buffered readers, like `parse_list_with_swears`.
+pub fn parse_reader_with_swears<R, S, I>(main_list: R, swears_lists: I,
+                                         options: &ParseOptions) -> Result<WordList>
+where
+    R: BufRead,
+    S: BufRead,
+    I: IntoIterator<Item = S>,
+{
+    parse_swears_annotated(main_list, swears_lists, options, |line| options.split_annotation(line))
+}
+
+/// Does the work of the other parse functions, reading the swears from every
+/// one of `swears_lists` and each line of `main_list` with `annotate`.
+fn parse_swears_annotated<R, S, I, F>(main_list: R, swears_lists: I, options: &ParseOptions,
+                                      annotate: F) -> Result<WordList>
+where
+    R: BufRead,
+    S: BufRead,
+    I: IntoIterator<Item = S>,
+    F: Fn(&str) -> (String, Option<Flag>),
+{
+    // Lines are trimmed before anything else, so that files with Windows line
+    // endings or stray spaces still have their annotations at the very end.
+    // Lines left blank are skipped.
+    let mut swears = vec![];
+    let mut seen = HashSet::new();
+    for swears_list in swears_lists {
+        for line in numbered_lines(swears_list) {
+            let (_, line) = line?;
+            let line = options.clean(line.trim());
+            if !line.is_empty() && seen.insert(line.clone()) {
+                swears.push(line);
+            }
+        }
+    }
+    // Swears are left out of the other lists whatever their case, so that a
+    // capitalized entry can't sneak one past the filter.
+    let swear_set: HashSet<String> = swears.iter().map(|swear| swear.to_lowercase()).collect();
 
     let mut normal_words = vec![];
     let mut uncountable_plurals = vec![];
     let mut neologisms = vec![];
+    let mut proper_nouns = vec![];
 
-    for line_result in main_lines {
-        let line = line_result?;
-        let (line_str, word_type) = if line.ends_with(NEOLOGISM_ANNOT) {
-            let mut line_trunc: String = String::from(line);
-            line_trunc.truncate(line_trunc.len() - NEOLOGISM_ANNOT.len_utf8());
-            (line_trunc, Some(Flag::Neologisms))
-        } else if line.ends_with(UNCOUNTABLE_PLURAL_ANNOT) {
-            let mut line_trunc: String = String::from(line);
-            line_trunc.truncate(line_trunc.len() -
-                                UNCOUNTABLE_PLURAL_ANNOT.len_utf8());
-            (line_trunc, Some(Flag::UncountablePlurals))
-        } else {
-            (line, None)
-        };
+    for line_result in numbered_lines(main_list) {
+        let (_, line) = line_result?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (line_str, word_type) = annotate(line.trim());
+        let line_str = options.clean(&line_str);
+        if !options.length_in_bounds(&line_str) {
+            continue;
+        }
 
-        if !swears.contains(&line_str) {
+        if !swear_set.contains(&line_str.to_lowercase()) {
             match word_type {
                 Some(Flag::UncountablePlurals) => {
                     uncountable_plurals.push(line_str);
@@ -135,6 +495,9 @@ pub fn parse_list<T: AsRef<Path>, U: AsRef<Path>>(main_list: T, swears_list: U)
                 Some(Flag::Neologisms) => {
                     neologisms.push(line_str);
                 }
+                Some(Flag::ProperNouns) => {
+                    proper_nouns.push(line_str);
+                }
                 Some(Flag::Swears) | None => {
                     normal_words.push(line_str);
                 }
@@ -142,62 +505,365 @@ pub fn parse_list<T: AsRef<Path>, U: AsRef<Path>>(main_list: T, swears_list: U)
         }
     }
 
-    Ok(WordList{
-        normal_words,
-        uncountable_plurals,
-        swears,
-        neologisms
+    swears.retain(|word| options.length_in_bounds(word));
+
+    Ok(WordList::new(normal_words, uncountable_plurals, swears, neologisms, proper_nouns))
+}
+
+/// Reads a list of words with their frequencies, one per line, as the word
+/// followed by whitespace and the frequency, like `the\t23135851162`. Lines
+/// with just a word give it a frequency of 0, and blank lines are skipped.
+/// Fails with `LexiError::Io` if the reader fails, or with `LexiError::Parse`
+/// if a line isn't valid UTF-8 or has a frequency that isn't a finite,
+/// non-negative number.
+pub fn parse_frequency_list<R: BufRead>(reader: R) -> Result<WeightedLexicon> {
+    let mut words = vec![];
+    for line in numbered_lines(reader) {
+        let (number, line) = line?;
+        let mut tokens = line.split_whitespace();
+        let word = match tokens.next() {
+            Some(word) => word,
+            None => continue,
+        };
+        let invalid = |message: String| LexiError::Parse{line: number, message};
+        let frequency = match (tokens.next(), tokens.next()) {
+            (None, _) => 0.0,
+            (Some(token), None) => token
+                .parse()
+                .ok()
+                .filter(|frequency: &f64| frequency.is_finite() && *frequency >= 0.0)
+                .ok_or_else(|| invalid(format!("invalid frequency {:?} for {:?}", token, word)))?,
+            (Some(_), Some(extra)) => return Err(invalid(format!("unexpected {:?} after the frequency", extra))),
+        };
+        words.push((word.to_string(), frequency));
+    }
+    Ok(WeightedLexicon::new(words))
+}
+
+/// Reads the lines of the reader along with their numbers, counting from 1.
+/// A line that isn't valid UTF-8 gives a `LexiError::Parse` for that line,
+/// and any other failure gives a `LexiError::Io`.
+fn numbered_lines<R: BufRead>(reader: R) -> impl Iterator<Item = Result<(usize, String)>> {
+    reader.lines().enumerate().map(|(i, line)| match line {
+        Ok(line) => Ok((i + 1, line)),
+        Err(error) if error.kind() == ErrorKind::InvalidData => {
+            Err(LexiError::Parse{line: i + 1, message: String::from("invalid UTF-8")})
+        }
+        Err(error) => Err(LexiError::Io(error)),
     })
 }
 
-/// Generates a WordList from the two input strings. The first one is the main
-/// word list, and marks neologisms with a trailing `!` and uncountable plurals
-/// with a trailing `#`.
-pub fn parse_strings(main_list: &str, swears_list: &str) -> Result<WordList> {
-    let main_lines = BufReader::new(main_list.as_bytes()).lines();
-    let swears_lines = BufReader::new(swears_list.as_bytes()).lines();
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
 
-    // I don't think this can fail?
-    let swears: Vec<String> = swears_lines.map(|l| l.unwrap()).collect();
+    use crate::lexicon::Lexicon;
 
-    let mut normal_words = vec![];
-    let mut uncountable_plurals = vec![];
-    let mut neologisms = vec![];
+    use super::*;
 
-    for line_result in main_lines {
-        let line = line_result?;
-        let (line_str, word_type) = if line.ends_with(NEOLOGISM_ANNOT) {
-            let mut line_trunc: String = String::from(line);
-            line_trunc.truncate(line_trunc.len() - NEOLOGISM_ANNOT.len_utf8());
-            (line_trunc, Some(Flag::Neologisms))
-        } else if line.ends_with(UNCOUNTABLE_PLURAL_ANNOT) {
-            let mut line_trunc: String = String::from(line);
-            line_trunc.truncate(line_trunc.len() -
-                                UNCOUNTABLE_PLURAL_ANNOT.len_utf8());
-            (line_trunc, Some(Flag::UncountablePlurals))
-        } else {
-            (line, None)
+    #[test]
+    fn test_swears_are_excluded() {
+        let list = parse_strings("apple\ndarn\nbanana!\nheck!\n", "darn\nheck\n").unwrap();
+        assert_eq!(list.normal_words, vec!["apple"]);
+        assert_eq!(list.neologisms, vec!["banana"]);
+        assert_eq!(list.swears, vec!["darn", "heck"]);
+    }
+
+    #[test]
+    fn test_default_annotations() {
+        let list = parse_strings("apple\nblogger!\nacnes%\n", "").unwrap();
+        assert_eq!(list.normal_words, vec!["apple"]);
+        assert_eq!(list.neologisms, vec!["blogger"]);
+        assert_eq!(list.uncountable_plurals, vec!["acnes"]);
+    }
+
+    #[test]
+    fn test_custom_annotations() {
+        let dir = std::env::temp_dir();
+        let main_path = dir.join(format!("lexi-custom-main-{}.txt", std::process::id()));
+        let swears_path = dir.join(format!("lexi-custom-swears-{}.txt", std::process::id()));
+        std::fs::write(&main_path, "apple\nblogger*\nacnes#\nwow!\n").unwrap();
+        std::fs::write(&swears_path, "").unwrap();
+
+        let options = ParseOptions{
+            neologism_annotation: '*',
+            uncountable_plural_annotation: '#',
+            ..ParseOptions::default()
         };
+        let result = parse_list_with(&main_path, &swears_path, &options);
+        std::fs::remove_file(&main_path).unwrap();
+        std::fs::remove_file(&swears_path).unwrap();
 
-        if !swears.contains(&line_str) {
-            match word_type {
-                Some(Flag::UncountablePlurals) => {
-                    uncountable_plurals.push(line_str);
-                },
-                Some(Flag::Neologisms) => {
-                    neologisms.push(line_str);
-                }
-                Some(Flag::Swears) | None => {
-                    normal_words.push(line_str);
-                }
-            }
+        let list = result.unwrap();
+        assert_eq!(list.normal_words, vec!["apple", "wow!"]);
+        assert_eq!(list.neologisms, vec!["blogger"]);
+        assert_eq!(list.uncountable_plurals, vec!["acnes"]);
+    }
+
+    #[test]
+    fn test_custom_annotator() {
+        let leading = |line: &str| match line.split_once(':') {
+            Some(("N", word)) => (word.to_string(), Some(Flag::Neologisms)),
+            Some(("U", word)) => (word.to_string(), Some(Flag::UncountablePlurals)),
+            _ => (line.to_string(), None),
+        };
+        let main = Cursor::new("apple\nN:blogger\nU:acnes\nwow!\nN:heck\nParis\n");
+        let list = parse_reader_annotated(main, Cursor::new("heck\n"), &ParseOptions::default(), leading)
+            .unwrap();
+        assert_eq!(list.normal_words, vec!["apple", "wow!", "Paris"]);
+        assert_eq!(list.neologisms, vec!["blogger"]);
+        assert_eq!(list.uncountable_plurals, vec!["acnes"]);
+        assert!(list.proper_nouns.is_empty());
+
+        let options = ParseOptions::default();
+        let main = "apple\nblogger!\nacnes%\nParis\n";
+        let annotated = parse_reader_annotated(main.as_bytes(), "".as_bytes(), &options,
+                                               |line| options.split_annotation(line));
+        assert_eq!(annotated.unwrap(), parse_strings(main, "").unwrap());
+    }
+
+    #[test]
+    fn test_crlf_line_endings() {
+        let list = parse_strings("apple\r\nblogger!\r\nacnes% \r\ndarn\r\r\n", "darn\r\n").unwrap();
+        assert_eq!(list.normal_words, vec!["apple"]);
+        assert_eq!(list.neologisms, vec!["blogger"]);
+        assert_eq!(list.uncountable_plurals, vec!["acnes"]);
+        assert_eq!(list.swears, vec!["darn"]);
+    }
+
+    #[test]
+    fn test_blank_lines_are_skipped() {
+        let list = parse_strings("\n  apple  \n\n   \n", "\n\t\n").unwrap();
+        assert_eq!(list.normal_words, vec!["apple"]);
+        assert!(list.swears.is_empty());
+        assert_eq!(list.default_list(), vec!["apple"]);
+    }
+
+    #[test]
+    fn test_multiple_swear_lists() {
+        let main = "apple\ndarn\nheck\nblimey!\nbanana\n";
+        let swears = vec![Cursor::new("darn\nheck\n"), Cursor::new("heck\nblimey")];
+        let list = parse_reader_with_swears(Cursor::new(main), swears, &ParseOptions::default()).unwrap();
+        assert_eq!(list.swears, vec!["darn", "heck", "blimey"]);
+        assert_eq!(list.default_list(), vec!["apple", "banana"]);
+
+        let dir = std::env::temp_dir();
+        let main_path = dir.join(format!("lexi-multi-main-{}.txt", std::process::id()));
+        let extra_path = dir.join(format!("lexi-multi-swears-{}.txt", std::process::id()));
+        std::fs::write(&main_path, main).unwrap();
+        std::fs::write(&extra_path, "blimey\n").unwrap();
+        let result = parse_list_with_swears(&main_path, &[crate::SWEARS_PATH.as_ref(), extra_path.as_path()],
+                                            &ParseOptions::default());
+        std::fs::remove_file(&main_path).unwrap();
+        std::fs::remove_file(&extra_path).unwrap();
+        let list = result.unwrap();
+        assert!(list.neologisms.is_empty());
+        assert!(list.swears.contains(&String::from("blimey")));
+        assert!(!list.default_list().contains(&String::from("blimey")));
+    }
+
+    #[test]
+    fn test_strip_apostrophes() {
+        let main = "don't\ncan't!\nit's\no'clock\n";
+        let list = parse_strings(main, "").unwrap();
+        assert_eq!(list.normal_words, vec!["don't", "it's", "o'clock"]);
+        assert_eq!(list.neologisms, vec!["can't"]);
+
+        let options = ParseOptions{strip_apostrophes: true, ..ParseOptions::default()};
+        let list = parse_reader_with(main.as_bytes(), "o'clock\n".as_bytes(), &options).unwrap();
+        assert_eq!(list.normal_words, vec!["dont", "its"]);
+        assert_eq!(list.neologisms, vec!["cant"]);
+        assert_eq!(list.swears, vec!["oclock"]);
+    }
+
+    #[test]
+    fn test_unreadable_swears_are_an_error() {
+        let path = std::env::temp_dir().join(format!("lexi-bad-swears-{}.txt", std::process::id()));
+        std::fs::write(&path, b"darn\n\xff\xfe\n").unwrap();
+        let result = parse_list(crate::MAIN_WORDLIST_PATH, &path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(LexiError::Parse{line: 2, ..})));
+    }
+
+    #[test]
+    fn test_missing_file_is_an_io_error() {
+        let path = std::env::temp_dir().join(format!("lexi-missing-{}.txt", std::process::id()));
+        match parse_list(&path, crate::SWEARS_PATH) {
+            Err(LexiError::Io(error)) => assert_eq!(error.kind(), ErrorKind::NotFound),
+            other => panic!("expected an IO error, got {:?}", other),
         }
+
+        let error: LexiError = std::io::Error::from(ErrorKind::PermissionDenied).into();
+        assert!(matches!(error, LexiError::Io(_)));
+        assert!(std::error::Error::source(&error).is_some());
     }
 
-    Ok(WordList{
-        normal_words,
-        uncountable_plurals,
-        swears,
-        neologisms
-    })
+    #[test]
+    fn test_large_swear_list() {
+        let main: Vec<String> = (0..5_000).map(|i| format!("word{}", i)).collect();
+        let swears: Vec<String> = (0..500).map(|i| format!("word{}", i * 10)).collect();
+        let list = parse_strings(&main.join("\n"), &swears.join("\n")).unwrap();
+        assert_eq!(list.normal_words.len(), 4_500);
+        assert_eq!(list.swears.len(), 500);
+        assert!(!list.normal_words.contains(&String::from("word10")));
+    }
+
+    #[test]
+    fn test_parse_reader() {
+        let main = Cursor::new("apple\ndarn\nblogger!\nacnes%\n");
+        let swears = Cursor::new("darn\n");
+        let list = parse_reader(main, swears).unwrap();
+        assert_eq!(list.normal_words, vec!["apple"]);
+        assert_eq!(list.neologisms, vec!["blogger"]);
+        assert_eq!(list.uncountable_plurals, vec!["acnes"]);
+        assert_eq!(list.swears, vec!["darn"]);
+    }
+
+    #[test]
+    fn test_parse_list_matches_parse_strings() {
+        let main = "apple\ndarn\nblogger!\nacnes%\n";
+        let swears = "darn\nheck\n";
+        let dir = std::env::temp_dir();
+        let main_path = dir.join(format!("lexi-equal-main-{}.txt", std::process::id()));
+        let swears_path = dir.join(format!("lexi-equal-swears-{}.txt", std::process::id()));
+        std::fs::write(&main_path, main).unwrap();
+        std::fs::write(&swears_path, swears).unwrap();
+        let from_files = parse_list(&main_path, &swears_path);
+        std::fs::remove_file(&main_path).unwrap();
+        std::fs::remove_file(&swears_path).unwrap();
+        assert_eq!(from_files.unwrap(), parse_strings(main, swears).unwrap());
+
+        let main = std::fs::read_to_string(crate::MAIN_WORDLIST_PATH).unwrap();
+        let swears = std::fs::read_to_string(crate::SWEARS_PATH).unwrap();
+        assert_eq!(parse_list(crate::MAIN_WORDLIST_PATH, crate::SWEARS_PATH).unwrap(),
+                   parse_strings(&main, &swears).unwrap());
+    }
+
+    #[test]
+    fn test_proper_nouns() {
+        let list = parse_strings("apple\nParis\nLondon\n", "").unwrap();
+        assert_eq!(list.proper_nouns, vec!["Paris", "London"]);
+
+        let default = VecLexicon::from(list.clone());
+        assert!(default.contains("apple"));
+        assert!(!default.contains("paris"));
+
+        let with_names = VecLexicon::new(list.custom_list(&[Flag::ProperNouns]));
+        assert!(with_names.contains("paris"));
+        assert!(with_names.contains("London"));
+    }
+
+    #[test]
+    fn test_annotations_beat_capitalization() {
+        let list = parse_strings("Damn!\nAcnes%\nBlogger!\nParis\n", "damn\n").unwrap();
+        assert_eq!(list.neologisms, vec!["Blogger"]);
+        assert_eq!(list.uncountable_plurals, vec!["Acnes"]);
+        assert_eq!(list.proper_nouns, vec!["Paris"]);
+        assert_eq!(list.swears, vec!["damn"]);
+        assert!(!VecLexicon::from(list).contains("damn"));
+    }
+
+    #[test]
+    fn test_length_bounds() {
+        let options = ParseOptions{min_length: Some(4), ..ParseOptions::default()};
+        let main = Cursor::new("at\nbe\nbear\nbeef!\ndarn\n");
+        let swears = Cursor::new("ass\ndarn\n");
+        let list = parse_reader_with(main, swears, &options).unwrap();
+        assert_eq!(list.normal_words, vec!["bear"]);
+        assert_eq!(list.neologisms, vec!["beef"]);
+        assert_eq!(list.swears, vec!["darn"]);
+
+        let list = parse_strings("at\nbe\nbear\n", "").unwrap();
+        assert_eq!(list.normal_words.len(), 3);
+        let options = ParseOptions{max_length: Some(2), ..ParseOptions::default()};
+        let list = parse_reader_with("at\nbe\nbear\n".as_bytes(), "".as_bytes(), &options).unwrap();
+        assert_eq!(list.normal_words, vec!["at", "be"]);
+    }
+
+    #[test]
+    fn test_accessors() {
+        let list = parse_list(crate::MAIN_WORDLIST_PATH, crate::SWEARS_PATH).unwrap();
+        assert!(list.neologisms().iter().any(|word| word == "blogger"));
+        assert!(!list.normal_words().iter().any(|word| word == "blogger"));
+        assert!(list.swears().iter().any(|word| word == "fuck"));
+        assert!(!list.uncountable_plurals().is_empty());
+        assert!(list.proper_nouns().is_empty());
+    }
+
+    #[test]
+    fn test_custom_list_flags() {
+        let list = parse_strings("apple\nblogger!\nacnes%\ndarn\n", "darn\n").unwrap();
+        assert_eq!(list.clone().custom_list(&[]), vec!["apple"]);
+        assert_eq!(list.clone().default_list(), vec!["apple", "blogger"]);
+        assert_eq!(list.clone().custom_list(&[Flag::Swears, Flag::UncountablePlurals, Flag::Swears]),
+                   vec!["apple", "acnes", "darn"]);
+        assert_eq!(list.custom_list(&[Flag::Neologisms, Flag::Swears, Flag::UncountablePlurals]),
+                   vec!["apple", "acnes", "darn", "blogger"]);
+    }
+
+    #[test]
+    fn test_merge() {
+        let base = parse_strings("apple\nbread\nblogger!\ndarn\n", "darn\n").unwrap();
+        let extra = parse_strings("apple\nstatin\nheck\nbreads%\nblogger!\n", "heck\n").unwrap();
+        let merged = base.merge(extra);
+        assert_eq!(merged.normal_words(), ["apple", "bread", "statin"]);
+        assert_eq!(merged.neologisms(), ["blogger"]);
+        assert_eq!(merged.uncountable_plurals(), ["breads"]);
+        assert_eq!(merged.swears(), ["darn", "heck"]);
+
+        let lex = VecLexicon::new(merged.clone().default_list());
+        assert!(lex.contains("statin"));
+        assert!(lex.contains("blogger"));
+        assert!(!lex.contains("breads"));
+        assert!(!lex.contains("heck"));
+        let lex = VecLexicon::new(merged.custom_list(&[Flag::Swears, Flag::UncountablePlurals]));
+        assert!(lex.contains("heck"));
+        assert!(lex.contains("breads"));
+        assert!(!lex.contains("blogger"));
+    }
+
+    #[test]
+    fn test_custom_list_keeps_file_order() {
+        let list = parse_strings("zebra\nblogger!\napple\nanime!\nmango\n", "").unwrap();
+        assert_eq!(list.default_list(), vec!["zebra", "apple", "mango", "blogger", "anime"]);
+    }
+
+    #[test]
+    fn test_parse_frequency_list() {
+        let list = "the\t5000\nof 3000.5\n\naardvark\n  cat   20\n";
+        let lex = parse_frequency_list(Cursor::new(list)).unwrap();
+        assert_eq!(lex.len(), 4);
+        assert_eq!(lex.weight("the"), Some(5000.0));
+        assert_eq!(lex.weight("of"), Some(3000.5));
+        assert_eq!(lex.weight("aardvark"), Some(0.0));
+        assert_eq!(lex.weight("cat"), Some(20.0));
+
+        let error = parse_frequency_list(Cursor::new("the 5000\nof lots\n")).unwrap_err();
+        assert!(matches!(error, LexiError::Parse{line: 2, ..}));
+        assert!(error.to_string().contains("line 2"));
+        assert!(parse_frequency_list(Cursor::new("the 5000 6000\n")).is_err());
+
+        let error = parse_frequency_list(Cursor::new("the 5000\nof NaN\n")).unwrap_err();
+        assert!(matches!(error, LexiError::Parse{line: 2, ..}));
+        let error = parse_frequency_list(Cursor::new("the -1\n")).unwrap_err();
+        assert!(matches!(error, LexiError::Parse{line: 1, ..}));
+        assert!(parse_frequency_list(Cursor::new("the inf\n")).is_err());
+    }
+
+    #[test]
+    fn test_censor() {
+        let list = parse_list(crate::MAIN_WORDLIST_PATH, crate::SWEARS_PATH).unwrap();
+        assert_eq!(list.censor("what the fuck"), "what the ****");
+        assert_eq!(list.censor("What the FUCK?!"), "What the ****?!");
+        assert_eq!(list.censor("(fuck), \"fuck\""), "(****), \"****\"");
+        assert_eq!(list.censor("a ball  gag"), "a ****  ***");
+        assert_eq!(list.censor("a ball and a gag"), "a ball and a gag");
+        assert_eq!(list.censor(""), "");
+        assert!(list.contains_swear("oh fuck."));
+        assert!(!list.contains_swear("oh fudge."));
+        assert!(!list.contains_swear("Scunthorpe"));
+
+        let list = parse_strings("apple\n", "swear1\nSwear Two\n").unwrap();
+        assert_eq!(list.censor("an apple, swear1! swear  two"), "an apple, ******! *****  ***");
+    }
 }